@@ -2,9 +2,121 @@ use std::error;
 use std::fmt;
 use std::io;
 
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Severity {
+    #[default]
+    Error,
+    /// The problem was recovered from without losing content.
+    Warning,
+}
+
+/// A parse error at a span of the source, lines and columns start at 1 and count chars.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// A suggestion how to fix the source.
+    pub fix: Option<String>,
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    /// The source line containing the span.
+    pub source: String,
+}
+
+impl Diagnostic {
+    /// Locates the span in the source, the span has to be a slice of the source.
+    pub fn new(message: String, source: &str, span: &str) -> Self {
+        let start = (span.as_ptr() as usize)
+            .saturating_sub(source.as_ptr() as usize)
+            .min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |index| start + index);
+        let end = (start + span.len()).min(line_end);
+        Self {
+            severity: Severity::default(),
+            message,
+            fix: None,
+            file: None,
+            line: source[..start].matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            length: source[start..end].chars().count(),
+            source: source[line_start..line_end].trim_end_matches('\r').into(),
+        }
+    }
+
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_fix(mut self, fix: &str) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+
+    /// Writes the message with the source line and carets below the span, e.g.
+    ///
+    /// ```text
+    /// error: unclosed chord, [G world
+    ///  --> song.cp:4:7
+    ///   |
+    /// 4 | Hello [G world
+    ///   |       ^^^^^^^^
+    /// ```
+    pub fn pretty(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let fix = self
+            .fix
+            .as_ref()
+            .map(|fix| format!("\n{} = help: {}", gutter, fix))
+            .unwrap_or_default();
+        format!(
+            "{}: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}{}",
+            severity,
+            self.message,
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.source,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.length.max(1)),
+            fix,
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     Parse(String),
+    /// A parse error with its location in the source.
+    Diagnostic(Box<Diagnostic>),
     Filesystem(String),
     Serialize(String),
     Http(String),
@@ -15,6 +127,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Parse(message) => write!(f, "ParseError ({})", message),
+            Self::Diagnostic(diagnostic) => write!(f, "ParseError ({})", diagnostic),
             Self::Filesystem(message) => write!(f, "FilesystemError ({})", message),
             Self::Serialize(message) => write!(f, "Serialize ({})", message),
             Self::Http(message) => write!(f, "Http ({})", message),
@@ -23,6 +136,34 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// Locates a parse error at the span of the source, other errors are kept as they are.
+    pub fn at(self, source: &str, span: &str) -> Self {
+        match self {
+            Self::Parse(message) => {
+                Self::Diagnostic(Box::new(Diagnostic::new(message, source, span)))
+            }
+            err => err,
+        }
+    }
+
+    /// Sets the file of a located parse error.
+    pub fn with_file(self, file: &str) -> Self {
+        match self {
+            Self::Diagnostic(diagnostic) => Self::Diagnostic(Box::new(diagnostic.with_file(file))),
+            err => err,
+        }
+    }
+
+    /// Writes located parse errors with their source line, see `Diagnostic::pretty`.
+    pub fn pretty(&self) -> String {
+        match self {
+            Self::Diagnostic(diagnostic) => diagnostic.pretty(),
+            err => format!("error: {}", err),
+        }
+    }
+}
+
 impl error::Error for Error {}
 
 impl From<io::Error> for Error {
@@ -37,6 +178,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<roxmltree::Error> for Error {
+    fn from(err: roxmltree::Error) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
+
 #[cfg(feature = "download")]
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
@@ -1,17 +1,72 @@
-use crate::error::Error;
-use crate::types::Part;
+use std::str::FromStr;
 
-pub struct PartIterator<'a> {
+use super::Recovery;
+use crate::error::{Error, Severity};
+use crate::types::{Chord, Part, SimpleChord};
+
+pub struct PartIterator<'a, 'b> {
+    /// The whole input the line is a slice of, to locate errors.
+    source: &'a str,
     line: &'a str,
+    key: &'a SimpleChord,
+    recovery: &'b mut Recovery,
 }
 
-impl<'a> PartIterator<'a> {
-    pub fn new(line: &'a str) -> Self {
-        Self { line }
+impl<'a, 'b> PartIterator<'a, 'b> {
+    pub fn new(
+        source: &'a str,
+        line: &'a str,
+        key: &'a SimpleChord,
+        recovery: &'b mut Recovery,
+    ) -> Self {
+        Self {
+            source,
+            line,
+            key,
+            recovery,
+        }
+    }
+
+    fn parse_chord(&self, chord: &str) -> Result<Chord, Error> {
+        Chord::from_str(chord).or_else(|err| {
+            Chord::from_nashville(chord)
+                .map(|chord| chord.transpose(self.key.level()))
+                .map_err(|_| err)
+        })
+    }
+
+    /// Keeps an unparsable chord as written in lenient mode.
+    fn part(&mut self, chord: &str, text: &str) -> Result<Part, Error> {
+        if chord.is_empty() {
+            return ("", text).try_into();
+        }
+        let (chord, raw_chord) = match self.parse_chord(chord) {
+            Ok(chord) => (Some(chord), None),
+            Err(_) => {
+                let err = Error::Parse(format!("invalid chord, {}", chord));
+                let mut fixed = chord.chars();
+                let fixed = fixed
+                    .next()
+                    .map(|first| first.to_uppercase().chain(fixed).collect::<String>())
+                    .filter(|fixed| self.parse_chord(fixed).is_ok())
+                    .map(|fixed| format!("write `{}`", fixed));
+                self.recovery.recover(
+                    err.at(self.source, chord),
+                    Severity::Warning,
+                    fixed.as_deref(),
+                )?;
+                (None, Some(chord.to_string()))
+            }
+        };
+        Ok(Part {
+            chord,
+            raw_chord,
+            languages: vec![text.to_string()],
+        })
     }
 }
 
-impl<'a> Iterator for PartIterator<'a> {
+impl Iterator for PartIterator<'_, '_> {
     type Item = Result<Part, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -19,22 +74,34 @@ impl<'a> Iterator for PartIterator<'a> {
             let text = &self.line[..idx];
             if idx != 0 {
                 self.line = &self.line[idx..];
-                return Some(("", text).try_into());
+                return Some(self.part("", text));
             } else {
-                self.line = &self.line[1..];
-                let idx = self.line.find("]").unwrap(); // TODO
-                let chord = &self.line[..idx];
+                let Some(idx) = self.line.find("]") else {
+                    let text = self.line;
+                    self.line = "";
+                    let err = Error::Parse(format!("unclosed chord, {}", text));
+                    return Some(
+                        self.recovery
+                            .recover(
+                                err.at(self.source, text),
+                                Severity::Warning,
+                                Some("close the chord with `]`"),
+                            )
+                            .and_then(|_| self.part("", text)),
+                    );
+                };
+                let chord = &self.line[1..idx];
                 self.line = &self.line[idx + 1..];
                 let idx = self.line.find("[").unwrap_or(self.line.len());
                 let text = &self.line[..idx];
                 self.line = &self.line[idx..];
-                return Some((chord, text).try_into());
+                return Some(self.part(chord, text));
             }
         }
-        if self.line.len() > 0 {
+        if !self.line.is_empty() {
             let text = self.line;
             self.line = "";
-            return Some(("", text).try_into());
+            return Some(self.part("", text));
         }
         None
     }
@@ -1,83 +1,145 @@
-pub struct SectionIterator<'a> {
-    title: &'a mut Option<String>,
-    key: &'a mut Option<String>,
-    artist: &'a mut Option<String>,
-    language: &'a mut Option<String>,
+use crate::types::Metadata;
+
+/// The directives collected as metadata of the song instead of being kept in a section, besides
+/// the fields of `Metadata`.
+pub static META_KEYS: &[&str] = &[
+    "title",
+    "subtitle",
+    "key",
+    "artist",
+    "language",
+    "capo",
+    "instrument",
+    "define",
+    "arrangement",
+    "meta",
+];
+
+/// Short forms of directives.
+static ALIASES: &[(&str, &str)] = &[
+    ("t", "title"),
+    ("st", "subtitle"),
+    ("c", "comment"),
+    ("ci", "comment_italic"),
+    ("cb", "comment_box"),
+    ("soc", "start_of_chorus"),
+    ("eoc", "end_of_chorus"),
+    ("sov", "start_of_verse"),
+    ("eov", "end_of_verse"),
+    ("sob", "start_of_bridge"),
+    ("eob", "end_of_bridge"),
+    ("sot", "start_of_tab"),
+    ("eot", "end_of_tab"),
+    ("sog", "start_of_grid"),
+    ("eog", "end_of_grid"),
+];
+
+/// The section titles of environments without a label.
+static ENVIRONMENTS: &[(&str, &str)] = &[
+    ("chorus", "Chorus"),
+    ("verse", "Verse"),
+    ("bridge", "Bridge"),
+    ("tab", "Tab"),
+    ("grid", "Grid"),
+];
+
+pub struct SectionIterator<'a, 'b> {
+    meta: &'b mut Vec<(&'a str, &'a str)>,
     section_title_cache: Option<&'a str>,
     lines_cache: Vec<&'a str>,
     lines: std::str::Lines<'a>,
 }
 
-impl<'a> SectionIterator<'a> {
-    pub fn new(
-        content: &'a str,
-        title: &'a mut Option<String>,
-        key: &'a mut Option<String>,
-        artist: &'a mut Option<String>,
-        language: &'a mut Option<String>,
-    ) -> Self {
+impl<'a, 'b> SectionIterator<'a, 'b> {
+    pub fn new(content: &'a str, meta: &'b mut Vec<(&'a str, &'a str)>) -> Self {
         Self {
-            title,
-            key,
-            artist,
-            language,
+            meta,
             section_title_cache: None,
             lines_cache: Vec::default(),
             lines: content.lines(),
         }
     }
-    fn parse_key_value(input: &str) -> Option<(&str, &str)> {
-        if input.starts_with('{') && input.ends_with('}') {
-            let inner = &input[1..input.len() - 1];
-            if let Some(index) = inner.find(':') {
-                let (key, value) = inner.split_at(index);
-                let key = key.trim();
-                let value = value[1..].trim();
-                if !key.is_empty() {
-                    return Some((key, value));
-                }
-            }
+    pub fn parse_key_value(input: &str) -> Option<(&str, &str)> {
+        let inner = input.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let index = inner
+            .find(|c: char| c == ':' || c.is_whitespace())
+            .unwrap_or(inner.len());
+        let (key, value) = inner.split_at(index);
+        let key = key.trim();
+        let value = value.trim_start();
+        let value = value.strip_prefix(':').unwrap_or(value).trim();
+        if key.is_empty() {
+            return None;
+        }
+        let key = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == key)
+            .map_or(key, |(_, key)| *key);
+        Some((key, value))
+    }
+
+    /// The label of an environment, given as `{soc: Chorus 2}` or `{soc label="Chorus 2"}`.
+    fn parse_label(value: &str) -> Option<&str> {
+        let label = value
+            .strip_prefix("label=")
+            .map(|label| label.trim_matches(|c| c == '"' || c == '\''))
+            .unwrap_or(value);
+        if label.is_empty() {
+            None
+        } else {
+            Some(label)
+        }
+    }
+
+    /// Finishes the current section and starts the next one, lines outside of any section are
+    /// kept as an untitled section unless they are blank.
+    fn take_section(&mut self, next: Option<&'a str>) -> Option<(&'a str, Vec<&'a str>)> {
+        let lines = std::mem::take(&mut self.lines_cache);
+        match std::mem::replace(&mut self.section_title_cache, next) {
+            Some(title) => Some((title, lines)),
+            None if lines.iter().any(|line| !line.trim().is_empty()) => Some(("", lines)),
+            None => None,
         }
-        None
     }
 }
 
-impl<'a> Iterator for SectionIterator<'a> {
+impl<'a> Iterator for SectionIterator<'a, '_> {
     type Item = (&'a str, Vec<&'a str>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let Some(line) = self.lines.next() {
-                if let Some((key, value)) = Self::parse_key_value(line) {
-                    match key {
-                        "title" => *self.title = Some(value.into()),
-                        "key" => *self.key = Some(value.into()),
-                        "artist" => *self.artist = Some(value.into()),
-                        "language" => *self.language = Some(value.into()),
-                        "section" => {
-                            if let Some(title) = self.section_title_cache {
-                                let lines_cache = std::mem::take(&mut self.lines_cache);
-                                self.section_title_cache = Some(value);
-                                return Some((title, lines_cache));
-                            } else {
-                                self.section_title_cache = Some(value);
-                                self.lines_cache = Vec::default();
-                            }
-                        }
-                        _ => self.lines_cache.push(line),
-                    }
-                } else {
-                    self.lines_cache.push(line);
+        while let Some(line) = self.lines.next() {
+            let Some((key, value)) = Self::parse_key_value(line) else {
+                self.lines_cache.push(line);
+                continue;
+            };
+            let section = if key == "section" {
+                self.take_section(Some(value))
+            } else if let Some(environment) = key.strip_prefix("start_of_") {
+                let title = Self::parse_label(value).unwrap_or(
+                    ENVIRONMENTS
+                        .iter()
+                        .find(|(name, _)| *name == environment)
+                        .map_or(environment, |(_, title)| *title),
+                );
+                self.take_section(Some(title))
+            } else if key.starts_with("end_of_") {
+                self.take_section(None)
+            } else if key == "meta" {
+                if let Some((name, value)) = value.split_once(char::is_whitespace) {
+                    self.meta.push((name, value.trim()));
                 }
+                None
+            } else if META_KEYS.contains(&key) || Metadata::KEYS.contains(&key) {
+                self.meta.push((key, value));
+                None
             } else {
-                if let Some(title) = self.section_title_cache {
-                    let lines_cache = std::mem::take(&mut self.lines_cache);
-                    self.section_title_cache = None;
-                    return Some((title, lines_cache));
-                } else {
-                    return None;
-                }
+                self.lines_cache.push(line);
+                None
+            };
+            if section.is_some() {
+                return section;
             }
         }
+        self.take_section(None)
     }
 }
@@ -1,41 +1,560 @@
 mod iter_part;
 mod iter_section;
 use iter_part::PartIterator;
-use iter_section::SectionIterator;
+use iter_section::{SectionIterator, META_KEYS};
 
-use crate::error::Error;
-use crate::types::{Line, Part, Section, Song};
+use std::str::FromStr;
+
+use crate::error::{Diagnostic, Error, Severity};
+use crate::types::{
+    Arrangement, Chord, Comment, CommentStyle, Fingering, Instrument, Key, Line, Metadata, Part,
+    Section, SimpleChord, Song,
+};
 
 pub fn load(path: &str) -> Result<Song, Error> {
-    load_string(&std::fs::read_to_string(path)?)
+    load_string(&std::fs::read_to_string(path)?).map_err(|err| err.with_file(path))
+}
+
+/// Loads the song like `load_lenient_string`, the diagnostics refer to the file.
+pub fn load_lenient(path: &str) -> Result<(Song, Vec<Diagnostic>), Error> {
+    let (song, diagnostics) = load_lenient_string(&std::fs::read_to_string(path)?);
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.with_file(path))
+        .collect();
+    Ok((song, diagnostics))
 }
 
 pub fn load_string(input: &str) -> Result<Song, Error> {
-    let mut title = None;
-    let mut key = None;
-    let mut artist = None;
-    let mut language = None;
+    parse(input, &mut Recovery::default())
+}
 
-    let sections = SectionIterator::new(input, &mut title, &mut key, &mut artist, &mut language)
-        .map(|(keyword, lines)| {
-            let lines = lines
-                .iter()
-                .map(|line| {
-                    let parts = PartIterator::new(line).collect::<Result<Vec<Part>, Error>>()?;
-                    Ok(Line::new(parts))
+/// Loads the song despite errors, unparsable chords are kept as written and invalid directives
+/// are skipped, every problem is reported as diagnostic.
+pub fn load_lenient_string(input: &str) -> (Song, Vec<Diagnostic>) {
+    let mut recovery = Recovery {
+        lenient: true,
+        diagnostics: Vec::default(),
+    };
+    // Located errors are all recovered, anything else is reported at the start of the input.
+    let song = match parse(input, &mut recovery) {
+        Ok(song) => song,
+        Err(Error::Diagnostic(diagnostic)) => {
+            recovery.diagnostics.push(*diagnostic);
+            Song::default()
+        }
+        Err(err) => {
+            recovery
+                .diagnostics
+                .push(Diagnostic::new(err.to_string(), input, &input[..0]));
+            Song::default()
+        }
+    };
+    (song, recovery.diagnostics)
+}
+
+/// Collects the problems of the lenient mode, the strict mode fails on the first one.
+#[derive(Default)]
+struct Recovery {
+    lenient: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Recovery {
+    fn recover(&mut self, err: Error, severity: Severity, fix: Option<&str>) -> Result<(), Error> {
+        match err {
+            Error::Diagnostic(diagnostic) if self.lenient => {
+                let diagnostic = diagnostic.with_severity(severity);
+                self.diagnostics.push(match fix {
+                    Some(fix) => diagnostic.with_fix(fix),
+                    None => diagnostic,
+                });
+                Ok(())
+            }
+            err => Err(err),
+        }
+    }
+
+    /// The value, or nothing if the error was recovered.
+    fn check<T>(&mut self, result: Result<T, Error>) -> Result<Option<T>, Error> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) => self.recover(err, Severity::Error, None).map(|_| None),
+        }
+    }
+}
+
+fn parse(input: &str, recovery: &mut Recovery) -> Result<Song, Error> {
+    let mut meta = Vec::default();
+    let sections = SectionIterator::new(input, &mut meta).collect::<Vec<(&str, Vec<&str>)>>();
+    let value = |name: &str| {
+        meta.iter()
+            .rev()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    };
+
+    let title = match value("title") {
+        Some(title) => title,
+        None => {
+            let err = Error::Parse("no title given".into()).at(input, &input[..0]);
+            recovery.recover(err, Severity::Warning, Some("add `{title: ...}`"))?;
+            ""
+        }
+    };
+    let artist = value("artist").or(value("subtitle")).map(String::from);
+    let language = value("language").map(String::from);
+    let key = recovery
+        .check(
+            value("key")
+                .map(|key| Key::try_from(key).map_err(|err| err.at(input, key)))
+                .transpose(),
+        )?
+        .flatten();
+    let capo = recovery
+        .check(
+            value("capo")
+                .map(|capo| {
+                    capo.parse::<u8>().map_err(|_| {
+                        Error::Parse(format!("invalid capo, {}", capo)).at(input, capo)
+                    })
+                })
+                .transpose(),
+        )?
+        .flatten();
+    let instrument = recovery
+        .check(
+            value("instrument")
+                .map(|instrument| {
+                    Instrument::try_from(instrument).map_err(|err| err.at(input, instrument))
                 })
-                .collect::<Result<Vec<Line>, Error>>()?;
-            Ok(Section::new(keyword.into(), lines))
+                .transpose(),
+        )?
+        .flatten();
+    let definitions = meta
+        .iter()
+        .filter(|(key, _)| *key == "define")
+        .map(|(_, definition)| {
+            let (chord, fingering) = definition.split_once(char::is_whitespace).ok_or(
+                Error::Parse(format!("no fingering given, {}", definition)).at(input, definition),
+            )?;
+            Ok((
+                Chord::from_str(chord).map_err(|err| err.at(input, chord))?,
+                Fingering::from_define(fingering).map_err(|err| err.at(input, fingering))?,
+            ))
+        })
+        .filter_map(|definition| recovery.check(definition).transpose())
+        .collect::<Result<Vec<(Chord, Fingering)>, Error>>()?;
+    let arrangements = meta
+        .iter()
+        .filter(|(key, _)| *key == "arrangement")
+        .map(|(_, arrangement)| {
+            Arrangement::try_from(*arrangement).map_err(|err| err.at(input, arrangement))
+        })
+        .filter_map(|arrangement| recovery.check(arrangement).transpose())
+        .collect::<Result<Vec<Arrangement>, Error>>()?;
+    let mut metadata = Metadata::default();
+    for (key, value) in meta
+        .iter()
+        .filter(|(key, _)| !META_KEYS.contains(key) || Metadata::KEYS.contains(key))
+    {
+        recovery.check(
+            metadata
+                .set(key, value)
+                .map(|_| ())
+                .map_err(|err| err.at(input, value)),
+        )?;
+    }
+    let tonic = key.as_ref().map(Key::tonic).cloned().unwrap_or_default();
+
+    let sections = sections
+        .into_iter()
+        .map(|(keyword, lines)| {
+            Ok(Section::new(
+                keyword.into(),
+                parse_lines(input, &lines, &tonic, recovery)?,
+            ))
         })
         .collect::<Result<Vec<Section>, Error>>()?;
 
     Ok(Song {
-        title: title.ok_or(Error::Parse("no title given".into()))?,
-        key: Some((key.ok_or(Error::Parse("no key given".into()))?.as_str()).try_into()?),
+        title: title.into(),
+        key,
         artist,
         language,
+        capo,
+        instrument,
+        definitions,
+        metadata,
+        arrangements,
         sections,
     }
+    .detect_key()
     .normalize()
     .clone())
 }
+
+/// Parses the lyrics and comments, `{translation: ...}` adds another language to the line before.
+fn parse_lines(
+    source: &str,
+    lines: &[&str],
+    tonic: &SimpleChord,
+    recovery: &mut Recovery,
+) -> Result<Vec<Line>, Error> {
+    let mut result: Vec<Line> = Vec::default();
+    for line in lines {
+        if let Some(comment) = parse_comment(line) {
+            result.push(comment.into());
+            continue;
+        }
+        let (text, translation) = match SectionIterator::parse_key_value(line) {
+            Some(("translation", text)) => (text, true),
+            _ => (*line, false),
+        };
+        let line = Line::new(
+            PartIterator::new(source, text, tonic, recovery)
+                .collect::<Result<Vec<Part>, Error>>()?,
+        );
+        match result.last_mut() {
+            Some(last) if translation && last.comment.is_none() => {
+                // without the chords of the line only the lyrics are added
+                let lyrics = line
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.languages.first())
+                    .map(String::as_str)
+                    .collect::<String>();
+                if let Err(err) = last.add_translation(line) {
+                    let fix = Some("use the chords of the line or none");
+                    recovery.recover(err.at(source, text), Severity::Warning, fix)?;
+                    last.add_translation(Line::new(vec![("", lyrics.as_str()).try_into()?]))?;
+                }
+            }
+            _ if translation => {
+                let err = Error::Parse(format!("no line to translate, {}", text)).at(source, text);
+                recovery.recover(err, Severity::Warning, Some("use a lyrics line instead"))?;
+                result.push(line);
+            }
+            _ => result.push(line),
+        }
+    }
+    Ok(result)
+}
+
+fn parse_comment(line: &str) -> Option<Comment> {
+    if let Some(text) = line.strip_prefix('#') {
+        return Some(Comment::new(text.trim().into(), CommentStyle::Hidden));
+    }
+    let (key, value) = SectionIterator::parse_key_value(line)?;
+    let style = match key {
+        "comment" | "highlight" => CommentStyle::Normal,
+        "comment_italic" => CommentStyle::Italic,
+        "comment_box" => CommentStyle::Boxed,
+        _ => return None,
+    };
+    Some(Comment::new(value.into(), style))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outputs::{FormatChordPro, Notation, Pitch};
+    use crate::types::{Chord, Mode, SectionKind, SimpleChord};
+
+    #[test]
+    fn load_nashville() {
+        let letters = "{title: Test}\n{key: G}\n{section: Verse}\n[G]Hello [C/B]dear [Em]world [F]";
+        let numbers =
+            "{title: Test}\n{key: G}\n{section: Verse}\n[1]Hello [4/3]dear [6m]world [b7]";
+
+        let song = load_string(numbers).unwrap();
+        assert_eq!(song, load_string(letters).unwrap());
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::Nashville, Pitch::default()),
+            numbers
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            letters
+        );
+    }
+
+    #[test]
+    fn load_minor_key() {
+        let letters =
+            "{title: Test}\n{key: Dm}\n{section: Verse}\n[Dm]Hello [Bb]dear [C]world [A7]";
+        let numbers =
+            "{title: Test}\n{key: Dm}\n{section: Verse}\n[1m]Hello [b6]dear [b7]world [57]";
+        let roman = "{title: Test}\n{key: Dm}\n{section: Verse}\n[i]Hello [VI]dear [VII]world [V7]";
+
+        let song = load_string(letters).unwrap();
+        assert_eq!(song.key, Some(Key::try_from("Dm").unwrap()));
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            letters
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::Nashville, Pitch::default()),
+            numbers
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::Roman, Pitch::default()),
+            roman
+        );
+    }
+
+    #[test]
+    fn load_without_key() {
+        let song =
+            load_string("{title: Test}\n{section: Verse}\n[Em]Hello [C]dear [G]world [D]").unwrap();
+        assert_eq!(song.key, Some(Key::new(SimpleChord::new(7), Mode::Minor)));
+        assert_eq!(song.chords()[0], Chord::new(7).minor());
+    }
+
+    #[test]
+    fn load_untitled_section() {
+        let input = "{title: Test}\n{key: C}\n[C]Intro\n{section: Verse}\n[G]Hello";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.sections[0].title, "");
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input
+        );
+    }
+
+    #[test]
+    fn load_capo() {
+        let sounding =
+            "{title: Test}\n{key: Bb}\n{capo: 3}\n{section: Verse}\n[Bb]Hello [Eb]dear [Gm]world [F]";
+        let shapes =
+            "{title: Test}\n{key: G}\n{capo: 3}\n{section: Verse}\n[G]Hello [C]dear [Em]world [D]";
+
+        let song = load_string(sounding).unwrap();
+        assert_eq!(song.capo, Some(3));
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::Sounding),
+            sounding
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::Shapes),
+            shapes
+        );
+        let reloaded = load_string(shapes).unwrap();
+        assert_eq!(reloaded.sections, song.sections);
+        assert_eq!(
+            (&reloaded).format_chord_pro(None, None, Notation::default(), Pitch::Sounding),
+            shapes
+        );
+        assert_eq!(song.suggest_capo(&Key::try_from("Bb").unwrap()), 3);
+        assert_eq!(song.suggest_capo(&Key::try_from("G").unwrap()), 0);
+    }
+
+    #[test]
+    fn load_define() {
+        let input = "{title: Test}\n{key: G}\n{define: Bm7 base-fret 2 frets x 1 3 1 2 1 fingers 0 1 3 1 2 1}\n{section: Verse}\n[Bm7]Hello";
+
+        let song = load_string(input).unwrap();
+        let chord = Chord::from_str("Bm7").unwrap();
+        assert_eq!(song.fingerings(&chord)[0].format(), "x24232");
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input
+        );
+        assert!(load_string("{title: Test}\n{define: Bm7}").is_err());
+    }
+
+    #[test]
+    fn load_instrument() {
+        let input = "{title: Test}\n{key: C}\n{instrument: ukulele}\n{section: Verse}\n[Am]Hello";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.instrument, Some(Instrument::Ukulele));
+        assert_eq!(song.fingerings(&song.chords()[0])[0].format(), "2000");
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input
+        );
+    }
+
+    #[test]
+    fn load_environments() {
+        let input = "{t: Test}\n{st: Someone}\n{key: C}\n[C]Intro\n{sov}\n[C]Hello\n{eov}\n\n{start_of_chorus: Chorus 2}\n[F]World\n{end_of_chorus}\n{start_of_bridge label=\"Bridge A\"}\n[G]Again\n{eob}\n{sot}\ne|--3--|\n{eot}";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.title, "Test");
+        assert_eq!(song.artist, Some("Someone".into()));
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| (section.title.as_str(), section.lines.len()))
+                .collect::<Vec<(&str, usize)>>(),
+            [
+                ("", 1),
+                ("Verse", 1),
+                ("Chorus 2", 1),
+                ("Bridge A", 1),
+                ("Tab", 1)
+            ]
+        );
+        assert_eq!(song.sections[2].kind, SectionKind::Chorus);
+        assert_eq!(song.sections[2].number, Some(2));
+    }
+
+    #[test]
+    fn load_comments() {
+        let input = "{title: Test}\n{key: C}\n{section: Verse}\n{c: half-time feel}\n[C]Hello\n{ci: only piano}\n{cb: loud}\n# not printed";
+        let output = "{title: Test}\n{key: C}\n{section: Verse}\n{comment: half-time feel}\n[C]Hello\n{comment_italic: only piano}\n{comment_box: loud}\n# not printed";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(
+            song.sections[0].comments()[0],
+            &Comment::new("half-time feel".into(), CommentStyle::Normal)
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            output
+        );
+    }
+
+    #[test]
+    fn load_translations() {
+        let input = "{title: Test}\n{key: C}\n{section: Verse}\nDu [C]bist [G]treu\n{translation: You [C]are [G]faithful}\n[F]Amen";
+
+        let song = load_string(input).unwrap();
+        let parts = &song.sections[0].lines[0].parts;
+        assert_eq!(parts[1].languages, ["bist ", "are "]);
+        assert_eq!(parts[2].languages, ["treu", "faithful"]);
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, Some(1), Notation::default(), Pitch::default()),
+            input.replace(
+                "Du [C]bist [G]treu\n{translation: You [C]are [G]faithful}",
+                "You [C]are [G]faithful"
+            )
+        );
+        assert!(load_string("{title: Test}\n{translation: Hello}").is_err());
+
+        let input =
+            "{title: Test}\n{key: C}\nDu [C]bist [G]treu\n{translation: You [C]are [F]faithful}";
+        assert!(load_string(input).is_err());
+        let (song, diagnostics) = load_lenient_string(input);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            song.sections[0].lines[0].parts[0].languages,
+            ["Du ", "You are faithful"]
+        );
+    }
+
+    #[test]
+    fn load_errors() {
+        let inputs = [
+            "{title: Test}\n{key: C}\n{section: Verse}\n[C]Hello [G world",
+            "{title: Test}\n{key: C}\n{section: Verse}\n[C]Hello\n[C]Wörld [Xyz]",
+            "{title: Test}\n{key: Q}",
+        ];
+        let outputs = [(4, 10, 8), (5, 11, 3), (2, 7, 1)];
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            match load_string(input) {
+                Err(Error::Diagnostic(diagnostic)) => assert_eq!(
+                    (diagnostic.line, diagnostic.column, diagnostic.length),
+                    *output
+                ),
+                result => panic!("expected a diagnostic, got {:?}", result),
+            }
+        }
+        assert_eq!(
+            load_string(inputs[0]).unwrap_err().with_file("test.cp").pretty(),
+            "error: unclosed chord, [G world\n --> test.cp:4:10\n  |\n4 | [C]Hello [G world\n  |          ^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn load_lenient() {
+        let input = "{title: Test}\n{key: C}\n{capo: high}\n{section: Verse}\n[C]Hello [am]dear [Xyz]world\n[G]Amen [F";
+
+        assert!(load_string(input).is_err());
+        let (song, diagnostics) = load_lenient_string(input);
+        assert_eq!(song.capo, None);
+        assert_eq!(
+            song.sections[0].lines[0].parts[2].raw_chord,
+            Some("Xyz".into())
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (
+                    diagnostic.severity,
+                    diagnostic.line,
+                    diagnostic.fix.as_deref()
+                ))
+                .collect::<Vec<(Severity, usize, Option<&str>)>>(),
+            [
+                (Severity::Error, 3, None),
+                (Severity::Warning, 5, Some("write `Am`")),
+                (Severity::Warning, 5, None),
+                (Severity::Warning, 6, Some("close the chord with `]`")),
+            ]
+        );
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input.replace("{capo: high}\n", "")
+        );
+    }
+
+    #[test]
+    fn load_lenient_overflows() {
+        let accidentals = "#".repeat(200);
+        let inputs = [
+            "{title: Test}\n{instrument: E30}\n[C]Hello".to_string(),
+            "{title: Test}\n{define: C base-fret 250 frets 9 9 9}\n[C]Hello".to_string(),
+            format!("{{title: Test}}\n{{key: C{}}}\n[C]Hello", accidentals),
+            format!("{{title: Test}}\n[C{}]Hello", accidentals),
+            "{title: Test}\n{duration: 4294967295:0}\n[C]Hello".to_string(),
+        ];
+        for input in &inputs {
+            assert!(load_string(input).is_err(), "{}", input);
+            let (song, diagnostics) = load_lenient_string(input);
+            assert_eq!(song.title, "Test", "{}", input);
+            assert_eq!(diagnostics.len(), 1, "{}", input);
+        }
+    }
+
+    #[test]
+    fn load_metadata() {
+        let input = "{title: Test}\n{key: C}\n{tempo: 72}\n{time: 6/8}\n{duration: 3:45}\n{ccli: 1234567}\n{year: 1998}\n{meta: lyricist Someone}\n{meta: arranger Someone else}\n{section: Verse}\n[C]Hello";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.metadata.tempo, Some(72));
+        assert_eq!(song.metadata.lyricist, Some("Someone".into()));
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input.replace("{meta: lyricist Someone}", "{lyricist: Someone}")
+        );
+    }
+
+    #[test]
+    fn load_arrangement() {
+        let input = "{title: Test}\n{key: C}\n{arrangement: Live: Verse, Chorus x2, Verse}\n{section: Verse}\n[C]Hello\n{section: Chorus}\n[F]World";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            input
+        );
+        let expanded = song.expand(Some("Live")).unwrap();
+        assert_eq!(
+            expanded
+                .sections
+                .iter()
+                .map(|section| section.title.as_str())
+                .collect::<Vec<&str>>(),
+            ["Verse", "Chorus", "Chorus", "Verse"]
+        );
+        assert!(expanded.arrangements.is_empty());
+        assert!(song.expand(Some("Studio")).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use super::iter_column::{is_ignored, split_columns, ColumnIterator};
+
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::types::{Chord, Key, Line, Metadata, Part, Section, SectionKind, Song};
+
+pub fn load(path: &str) -> Result<Song, Error> {
+    load_string(&std::fs::read_to_string(path)?).map_err(|err| err.with_file(path))
+}
+
+/// Loads plain text with the chords in a line above the lyrics. The first line is the title
+/// unless it is a chord line or a section header, lines like `Key: G` or `Capo: 2` before the
+/// first section are read as metadata and sections start with headers like `[Chorus]` or
+/// `Verse 1:`.
+pub fn load_string(input: &str) -> Result<Song, Error> {
+    // Blank lines are kept, they end a chord line without lyrics below it.
+    let mut lines = input.lines().map(str::trim_end).peekable();
+    while lines.next_if(|line| line.is_empty()).is_some() {}
+    let second = lines.clone().nth(1);
+    let title = lines
+        .next_if(|line| !is_chord_line(line, second) && parse_header(line).is_none())
+        .unwrap_or_default();
+    let mut artist = None;
+    let mut key = None;
+    let mut capo = None;
+    loop {
+        while lines.next_if(|line| line.is_empty()).is_some() {}
+        let Some((name, value)) = lines.peek().and_then(|line| parse_meta(line)) else {
+            break;
+        };
+        match name.as_str() {
+            "artist" => artist = Some(value.to_string()),
+            "key" => key = Some(Key::try_from(value).map_err(|err| err.at(input, value))?),
+            _ => {
+                capo = Some(value.parse::<u8>().map_err(|_| {
+                    Error::Parse(format!("invalid capo, {}", value)).at(input, value)
+                })?)
+            }
+        }
+        lines.next();
+    }
+
+    let mut sections = Vec::default();
+    let mut section_title = "";
+    let mut section_lines = Vec::default();
+    while let Some(line) = lines.next() {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = parse_header(line) {
+            if !section_title.is_empty() || !section_lines.is_empty() {
+                sections.push(Section::new(
+                    section_title.into(),
+                    std::mem::take(&mut section_lines),
+                ));
+            }
+            section_title = header;
+            continue;
+        }
+        let parts = if is_chord_line(line, lines.peek().copied()) {
+            let text = lines.next_if(|next| is_lyrics(next)).unwrap_or_default();
+            ColumnIterator::new(input, split_columns(line), text)
+                .collect::<Result<Vec<Part>, Error>>()?
+        } else {
+            vec![("", line).try_into()?]
+        };
+        section_lines.push(Line::new(parts));
+    }
+    if !section_title.is_empty() || !section_lines.is_empty() {
+        sections.push(Section::new(section_title.into(), section_lines));
+    }
+
+    Ok(Song {
+        title: title.trim().into(),
+        key,
+        artist,
+        language: None,
+        capo,
+        instrument: None,
+        definitions: Vec::default(),
+        metadata: Metadata::default(),
+        arrangements: Vec::default(),
+        sections,
+    }
+    .detect_key()
+    .normalize()
+    .clone())
+}
+
+/// Whether every word of the line is a chord, bar lines and repeats aside.
+fn is_chords(line: &str) -> bool {
+    let mut words = line
+        .split_whitespace()
+        .filter(|word| !is_ignored(word))
+        .peekable();
+    words.peek().is_some()
+        && words.all(|word| Chord::from_str(word).is_ok_and(|chord| chord.unparsed().is_empty()))
+}
+
+/// Whether the line holds chords, a single word like `A` could be lyrics as well and is only a
+/// chord line with lyrics below it.
+fn is_chord_line(line: &str, next: Option<&str>) -> bool {
+    let words = line
+        .split_whitespace()
+        .filter(|word| !is_ignored(word))
+        .count();
+    is_chords(line) && (words > 1 || next.is_some_and(is_lyrics))
+}
+
+fn is_lyrics(line: &str) -> bool {
+    !line.is_empty() && !is_chords(line) && parse_header(line).is_none()
+}
+
+/// The title of a section header like `[Chorus]` or `Verse 1:`.
+fn parse_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if let Some(title) = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+    {
+        return Some(title.trim());
+    }
+    let title = line.strip_suffix(':')?.trim();
+    match SectionKind::parse(title).0 {
+        SectionKind::Custom => None,
+        _ => Some(title),
+    }
+}
+
+/// The lowercase name and the value of a metadata line like `Key: G`.
+fn parse_meta(line: &str) -> Option<(String, &str)> {
+    let (name, value) = line.split_once(':')?;
+    let name = name.trim().to_lowercase();
+    let value = value.trim();
+    match name.as_str() {
+        "artist" | "key" | "capo" if !value.is_empty() => Some((name, value)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::outputs::{FormatChordPro, Notation, Pitch};
+
+    #[test]
+    fn load_chords_over_lyrics() {
+        let input = "Amazing Grace\nArtist: John Newton\nKey: G\nCapo: 2\n\nVerse 1:\n        G          G7\nAmazing grace, how sweet\nC   G\nthe sound\n\n[Chorus]\nD  | Em x2\n   Hallelujah\nEnd\n";
+        let output = "{title: Amazing Grace}\n{key: G}\n{capo: 2}\n{artist: John Newton}\n{section: Verse 1}\nAmazing [G]grace, how [G7]sweet\n[C]the [G]sound\n{section: Chorus}\n[D]   Ha[Em]llelujah\nEnd";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.sections[0].kind, SectionKind::Verse);
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            output
+        );
+    }
+
+    #[test]
+    fn load_without_title() {
+        let song = load_string("G   C\nHello world\n").unwrap();
+        assert_eq!(song.title, "");
+        assert_eq!(song.sections[0].lines[0].parts.len(), 2);
+
+        let song = load_string("[Verse]\nHello world\n").unwrap();
+        assert_eq!(song.title, "");
+        assert_eq!(song.sections[0].title, "Verse");
+    }
+
+    #[test]
+    fn load_single_word_lines() {
+        let input = "Test\nKey: C\n\n[Verse]\nA\n\nG\nHello\nA\nsong";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            "{title: Test}\n{key: C}\n{section: Verse}\nA\n[G]Hello\n[A]song"
+        );
+        assert_eq!(load_string("A\n\nHello").unwrap().title, "A");
+    }
+
+    #[test]
+    fn load_chord_line_before_blank_line() {
+        let input = "Test\nKey: G\n\n[Intro]\nG  C\n\nHello world\n";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.sections[0].lines.len(), 2);
+        assert_eq!(
+            (&song).format_chord_pro(None, None, Notation::default(), Pitch::default()),
+            "{title: Test}\n{key: G}\n{section: Intro}\n[G][C]\nHello world"
+        );
+    }
+}
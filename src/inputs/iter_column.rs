@@ -0,0 +1,160 @@
+use crate::error::Error;
+use crate::types::Part;
+
+/// Splits a lyrics line at the columns of the chords in the line above.
+pub struct ColumnIterator<'a> {
+    /// The whole input the lines are slices of, to locate errors.
+    source: &'a str,
+    /// The chords with the column they start at.
+    chords: Vec<(usize, &'a str)>,
+    index: usize,
+    text: &'a str,
+    column: usize,
+    done: bool,
+}
+
+impl<'a> ColumnIterator<'a> {
+    /// Takes the chords with the column they start at, the chords have to be slices of the
+    /// source.
+    pub fn new(source: &'a str, chords: Vec<(usize, &'a str)>, text: &'a str) -> Self {
+        Self {
+            source,
+            chords,
+            index: 0,
+            text,
+            column: 0,
+            done: false,
+        }
+    }
+
+    fn part(&self, chord: &str, text: &str) -> Result<Part, Error> {
+        (chord, text).try_into().map_err(|_: Error| {
+            Error::Parse(format!("invalid chord, {}", chord)).at(self.source, chord)
+        })
+    }
+
+    /// Takes the text up to the column, or the rest of the text.
+    fn take(&mut self, column: Option<usize>) -> &'a str {
+        let byte_index = match column {
+            Some(column) => self
+                .text
+                .char_indices()
+                .nth(column - self.column)
+                .map_or(self.text.len(), |(byte_index, _)| byte_index),
+            None => self.text.len(),
+        };
+        let text = &self.text[..byte_index];
+        self.text = &self.text[byte_index..];
+        if let Some(column) = column {
+            self.column = column;
+        }
+        text
+    }
+}
+
+impl Iterator for ColumnIterator<'_> {
+    type Item = Result<Part, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chord, end) = match self.chords.get(self.index) {
+            Some((column, _)) if *column > self.column && !self.text.is_empty() => {
+                ("", Some(*column))
+            }
+            Some((_, chord)) => {
+                self.index += 1;
+                (
+                    *chord,
+                    self.chords.get(self.index).map(|(column, _)| *column),
+                )
+            }
+            // A line without chords is a single part, even if it is empty.
+            None if !self.text.is_empty() || (self.chords.is_empty() && !self.done) => ("", None),
+            None => return None,
+        };
+        self.done = true;
+        let text = self.take(end);
+        Some(self.part(chord, text))
+    }
+}
+
+/// The words of a chord line with the column they start at, bar lines and repeats aside.
+pub fn split_columns(line: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::default();
+    let mut start = None;
+    for (column, (byte_index, c)) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+        .enumerate()
+    {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column, byte_index)),
+            (Some((column, start_index)), true) => {
+                let word = &line[start_index..byte_index];
+                if !is_ignored(word) {
+                    result.push((column, word));
+                }
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    result
+}
+
+/// Bar lines and repeats like `x2` in a chord line.
+pub fn is_ignored(word: &str) -> bool {
+    word.chars()
+        .all(|c| matches!(c, '|' | '-' | '/' | '%' | '.' | ':'))
+        || word
+            .strip_prefix(['x', 'X'])
+            .is_some_and(|repeat| !repeat.is_empty() && repeat.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn column_iterator() {
+        let inputs = [
+            ("A", ""),
+            ("A  B", ""),
+            ("A    B", "Heyö you"),
+            ("A          B", "Hey"),
+            ("    A", "Hey you"),
+            ("", "Hello World"),
+            ("", ""),
+            ("A | B", "Hey you"),
+        ];
+        let outputs = [
+            vec![("A", "").try_into().unwrap()],
+            vec![("A", "").try_into().unwrap(), ("B", "").try_into().unwrap()],
+            vec![
+                ("A", "Heyö ").try_into().unwrap(),
+                ("B", "you").try_into().unwrap(),
+            ],
+            vec![
+                ("A", "Hey").try_into().unwrap(),
+                ("B", "").try_into().unwrap(),
+            ],
+            vec![
+                ("", "Hey ").try_into().unwrap(),
+                ("A", "you").try_into().unwrap(),
+            ],
+            vec![("", "Hello World").try_into().unwrap()],
+            vec![("", "").try_into().unwrap()],
+            vec![
+                ("A", "Hey ").try_into().unwrap(),
+                ("B", "you").try_into().unwrap(),
+            ],
+        ];
+        for ((chord_line, text), output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(
+                &ColumnIterator::new(chord_line, split_columns(chord_line), text)
+                    .collect::<Result<Vec<Part>, Error>>()
+                    .unwrap(),
+                output
+            );
+        }
+    }
+}
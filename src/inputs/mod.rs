@@ -1,2 +1,8 @@
+mod iter_column;
+
 pub mod chord_pro;
+pub mod chords_over_lyrics;
+pub mod open_lyrics;
+pub mod open_song;
+pub mod song_beamer;
 pub mod ultimate_guitar;
@@ -0,0 +1,370 @@
+use std::str::FromStr;
+
+use roxmltree::{Document, Node};
+
+use crate::error::Error;
+use crate::types::{
+    section_title, Arrangement, Chord, Comment, CommentStyle, Key, Line, Metadata, Part, Section,
+    Song, OPEN_LYRICS_CODES,
+};
+
+/// The chord suffixes of the OpenLyrics chord structures, other structures are read as suffixes.
+static STRUCTURES: &[(&str, &str)] = &[
+    ("maj", ""),
+    ("min", "m"),
+    ("aug", "aug"),
+    ("dim", "dim"),
+    ("dom7", "7"),
+    ("maj7", "maj7"),
+    ("min7", "m7"),
+    ("minmaj7", "mmaj7"),
+    ("dim7", "dim7"),
+    ("halfdim7", "m7b5"),
+    ("dom7sus4", "7sus4"),
+    ("dom9", "9"),
+    ("maj9", "maj9"),
+    ("min9", "m9"),
+    ("min6", "m6"),
+];
+
+/// The lines of a verse in one language, untagged lines have no language.
+type Group<'a> = (Option<&'a str>, Vec<Line>);
+
+pub fn load(path: &str) -> Result<Song, Error> {
+    load_string(&std::fs::read_to_string(path)?).map_err(|err| err.with_file(path))
+}
+
+/// Loads an OpenLyrics song, verses of other languages are merged as translations into the lines
+/// of the main language, which is the untagged one or else the first. The verse order becomes an
+/// arrangement.
+pub fn load_string(input: &str) -> Result<Song, Error> {
+    let document = Document::parse(input)?;
+    let root = document.root_element();
+    let properties = child(root, "properties");
+    let property = |name: &'static str| properties.and_then(|properties| text(properties, name));
+
+    let title = properties
+        .and_then(|properties| child(properties, "titles"))
+        .and_then(|titles| text(titles, "title"))
+        .ok_or(Error::Parse("no title given".into()))?;
+    let mut artist = None;
+    let mut metadata = Metadata::default();
+    for author in properties
+        .and_then(|properties| child(properties, "authors"))
+        .iter()
+        .flat_map(|authors| children(*authors, "author"))
+    {
+        let name = author.text().unwrap_or_default().trim();
+        match author.attribute("type") {
+            Some("music") => metadata.composer = Some(name.into()),
+            Some("words") => metadata.lyricist = Some(name.into()),
+            Some(_) => (),
+            None => artist = artist.or(Some(name.into())),
+        }
+    }
+    metadata.copyright = property("copyright").map(String::from);
+    if let Some(ccli) = property("ccliNo") {
+        metadata.set("ccli", ccli)?;
+    }
+    if let Some(year) = property("released").filter(|year| year.parse::<u16>().is_ok()) {
+        metadata.set("year", year)?;
+    }
+    if let Some(tempo) = properties
+        .and_then(|properties| child(properties, "tempo"))
+        .filter(|tempo| tempo.attribute("type") == Some("bpm"))
+        .and_then(|tempo| tempo.text())
+    {
+        metadata.set("tempo", tempo)?;
+    }
+    let key = property("key").map(Key::try_from).transpose()?;
+
+    let mut languages: Vec<Option<&str>> = Vec::default();
+    let mut verses: Vec<(&str, Vec<Group>)> = Vec::default();
+    for verse in child(root, "lyrics")
+        .iter()
+        .flat_map(|lyrics| children(*lyrics, "verse"))
+    {
+        let name = verse
+            .attribute("name")
+            .ok_or(Error::Parse("verse without name".into()))?;
+        let index = match verses.iter().position(|(other, _)| *other == name) {
+            Some(index) => index,
+            None => {
+                verses.push((name, Vec::default()));
+                verses.len() - 1
+            }
+        };
+        for lines in children(verse, "lines") {
+            let language = lines.attribute("lang").or(verse.attribute("lang"));
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+            let parsed = parse_lines(lines)?;
+            let groups = &mut verses[index].1;
+            match groups.iter_mut().find(|(other, _)| *other == language) {
+                Some((_, group)) => group.extend(parsed),
+                None => groups.push((language, parsed)),
+            }
+        }
+    }
+    // The untagged lines, or else the first language, are the main language of the song.
+    if let Some(index) = languages.iter().position(Option::is_none) {
+        let main = languages.remove(index);
+        languages.insert(0, main);
+    }
+
+    let mut sections = Vec::default();
+    for (name, mut groups) in verses {
+        groups.sort_by_key(|(language, _)| languages.iter().position(|other| other == language));
+        let mut groups = groups.into_iter();
+        let mut lines = groups.next().map(|(_, lines)| lines).unwrap_or_default();
+        for (_, translations) in groups {
+            for (line, translation) in lines.iter_mut().zip(translations) {
+                line.add_translation(translation)?;
+            }
+        }
+        sections.push(Section::new(section_title(name, OPEN_LYRICS_CODES), lines));
+    }
+
+    let arrangements = property("verseOrder")
+        .map(|order| {
+            Arrangement::from_sections(
+                String::default(),
+                order
+                    .split_whitespace()
+                    .map(|name| section_title(name, OPEN_LYRICS_CODES)),
+            )
+        })
+        .into_iter()
+        .collect();
+
+    Ok(Song {
+        title: title.into(),
+        key,
+        artist,
+        language: languages.iter().any(Option::is_some).then(|| {
+            languages
+                .iter()
+                .map(|code| code.unwrap_or_default())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        }),
+        capo: None,
+        instrument: None,
+        definitions: Vec::default(),
+        metadata,
+        arrangements,
+        sections,
+    }
+    .detect_key()
+    .normalize()
+    .clone())
+}
+
+fn children<'a, 'b>(node: Node<'a, 'b>, name: &'static str) -> impl Iterator<Item = Node<'a, 'b>> {
+    node.children()
+        .filter(move |child| child.tag_name().name() == name)
+}
+
+fn child<'a, 'b>(node: Node<'a, 'b>, name: &'static str) -> Option<Node<'a, 'b>> {
+    children(node, name).next()
+}
+
+fn text<'a>(node: Node<'a, '_>, name: &'static str) -> Option<&'a str> {
+    child(node, name)?
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Splits the lines at `<br/>`, chords start a new part and comments become comment lines.
+fn parse_lines(lines: Node) -> Result<Vec<Line>, Error> {
+    let mut result = Vec::default();
+    let mut parts = Vec::default();
+    parse_content(lines, &mut result, &mut parts)?;
+    finish_line(&mut result, &mut parts);
+    Ok(result)
+}
+
+fn parse_content(node: Node, result: &mut Vec<Line>, parts: &mut Vec<Part>) -> Result<(), Error> {
+    for child in node.children() {
+        if child.is_text() {
+            let text = collapse(child.text().unwrap_or_default());
+            match parts.last_mut() {
+                Some(Part { languages, .. }) => languages[0].push_str(&text),
+                None if !text.trim().is_empty() => parts.push(("", text.trim_start()).try_into()?),
+                None => (),
+            }
+            continue;
+        }
+        match child.tag_name().name() {
+            "chord" => {
+                let chord = parse_chord(child)?;
+                parts.push(Part {
+                    chord: Some(chord),
+                    raw_chord: None,
+                    languages: vec![String::default()],
+                });
+                parse_content(child, result, parts)?;
+            }
+            "br" => finish_line(result, parts),
+            "comment" => {
+                finish_line(result, parts);
+                let text = child.text().unwrap_or_default().trim();
+                result.push(Comment::new(text.into(), CommentStyle::Normal).into());
+            }
+            _ => parse_content(child, result, parts)?,
+        }
+    }
+    Ok(())
+}
+
+/// Reads the `name` of a chord, or its `root` with the optional `structure`, and the `bass`.
+fn parse_chord(node: Node) -> Result<Chord, Error> {
+    let name = match (node.attribute("name"), node.attribute("root")) {
+        (Some(name), _) => name.to_string(),
+        (None, Some(root)) => {
+            let structure = node.attribute("structure").unwrap_or_default();
+            let suffix = STRUCTURES
+                .iter()
+                .find(|(other, _)| *other == structure)
+                .map_or(structure, |(_, suffix)| *suffix);
+            format!("{}{}", root, suffix)
+        }
+        (None, None) => return Err(Error::Parse("chord without name".into())),
+    };
+    let name = match node.attribute("bass") {
+        Some(bass) => format!("{}/{}", name, bass),
+        None => name,
+    };
+    let chord = Chord::from_str(&name)?;
+    // an unknown structure is not silently kept as a variation of the root
+    if node.attribute("name").is_none() && !chord.unparsed().is_empty() {
+        return Err(Error::Parse(format!(
+            "unknown chord structure, {}",
+            node.attribute("structure").unwrap_or_default()
+        )));
+    }
+    Ok(chord)
+}
+
+/// Joins the lines of the text with a space, dropping the indentation of the XML.
+fn collapse(text: &str) -> String {
+    if !text.contains('\n') {
+        return text.into();
+    }
+    let lines = text.split('\n').collect::<Vec<&str>>();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| match index {
+            0 => line.trim_end(),
+            _ if index + 1 == lines.len() => line.trim_start(),
+            _ => line.trim(),
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn finish_line(result: &mut Vec<Line>, parts: &mut Vec<Part>) {
+    if let Some(Part { languages, .. }) = parts.last_mut() {
+        languages[0] = languages[0].trim_end().into();
+    }
+    if !parts.is_empty() {
+        result.push(Line::new(std::mem::take(parts)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::SectionKind;
+
+    #[test]
+    fn load_indented() {
+        let input = r#"<song xmlns="http://openlyrics.info/namespace/2009/song" version="0.9">
+  <properties>
+    <titles><title>Test</title></titles>
+  </properties>
+  <lyrics>
+    <verse name="c">
+      <lines>
+        <chord root="D" bass="F#"/>Hello <chord root="G" structure="min7">world</chord>
+        <br/>
+        and
+        goodbye
+      </lines>
+    </verse>
+  </lyrics>
+</song>"#;
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.sections[0].kind, SectionKind::Chorus);
+        assert_eq!(
+            song.chords(),
+            [
+                Chord::from_str("D/F#").unwrap(),
+                Chord::from_str("Gm7").unwrap()
+            ]
+        );
+        assert_eq!(
+            song.sections[0]
+                .lines
+                .iter()
+                .map(|line| line
+                    .parts
+                    .iter()
+                    .map(|part| part.languages[0].as_str())
+                    .collect::<String>())
+                .collect::<Vec<String>>(),
+            ["Hello world", "and goodbye"]
+        );
+        assert!(load_string("<song><properties></properties></song>").is_err());
+        assert!(load_string(&input.replace("min7", "wobbly")).is_err());
+    }
+
+    #[test]
+    fn load_translations() {
+        let input = r#"<song>
+  <properties><titles><title>Test</title></titles></properties>
+  <lyrics>
+    <verse name="v1" lang="de"><lines>Strophe</lines></verse>
+    <verse name="v1" lang="en"><lines>Verse</lines></verse>
+    <verse name="c" lang="en"><lines>Chorus</lines></verse>
+    <verse name="c" lang="de"><lines>Refrain</lines></verse>
+  </lyrics>
+</song>"#;
+        let languages = |song: &Song| {
+            song.sections
+                .iter()
+                .map(|section| {
+                    section
+                        .lines
+                        .iter()
+                        .map(|line| line.parts[0].languages.clone())
+                        .collect::<Vec<Vec<String>>>()
+                })
+                .collect::<Vec<Vec<Vec<String>>>>()
+        };
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.language, Some("de, en".into()));
+        assert_eq!(
+            languages(&song),
+            [[["Strophe", "Verse"]], [["Refrain", "Chorus"]]]
+        );
+
+        let song = load_string(
+            &input
+                .replace(" lang=\"de\"><lines>Strophe", "><lines>Strophe")
+                .replace(" lang=\"de\"><lines>Refrain", "><lines>Refrain"),
+        )
+        .unwrap();
+        assert_eq!(song.language, Some(", en".into()));
+        assert_eq!(
+            languages(&song),
+            [[["Strophe", "Verse"]], [["Refrain", "Chorus"]]]
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use roxmltree::{Document, Node};
+
+use super::iter_column::{split_columns, ColumnIterator};
+use crate::error::Error;
+use crate::types::{
+    section_title, Arrangement, Comment, CommentStyle, Key, Line, Metadata, Part, Section, Song,
+    OPEN_SONG_CODES,
+};
+
+pub fn load(path: &str) -> Result<Song, Error> {
+    load_string(&std::fs::read_to_string(path)?).map_err(|err| err.with_file(path))
+}
+
+/// Loads an OpenSong song, the lyrics have section markers like `[V1]`, chord lines starting
+/// with `.`, lyrics lines starting with a space and comments starting with `;`.
+pub fn load_string(input: &str) -> Result<Song, Error> {
+    let document = Document::parse(input)?;
+    let root = document.root_element();
+    let text = |name: &'static str| text(root, name);
+
+    let mut metadata = Metadata {
+        copyright: text("copyright").map(String::from),
+        time: text("time_sig").map(String::from),
+        ..Default::default()
+    };
+    if let Some(ccli) = text("ccli") {
+        metadata.set("ccli", ccli)?;
+    }
+    if let Some(tempo) = text("tempo").filter(|tempo| tempo.parse::<u16>().is_ok()) {
+        metadata.set("tempo", tempo)?;
+    }
+    let capo = text("capo")
+        .map(|capo| {
+            capo.parse::<u8>()
+                .map_err(|_| Error::Parse(format!("invalid capo, {}", capo)))
+        })
+        .transpose()?;
+    let arrangements = text("presentation")
+        .map(|order| {
+            Arrangement::from_sections(
+                String::default(),
+                order
+                    .split_whitespace()
+                    .map(|name| section_title(name, OPEN_SONG_CODES)),
+            )
+        })
+        .into_iter()
+        .collect();
+
+    Ok(Song {
+        title: text("title")
+            .ok_or(Error::Parse("no title given".into()))?
+            .into(),
+        key: text("key").map(Key::try_from).transpose()?,
+        artist: text("author").map(String::from),
+        language: None,
+        capo,
+        instrument: None,
+        definitions: Vec::default(),
+        metadata,
+        arrangements,
+        sections: parse_lyrics(text("lyrics").unwrap_or_default())?,
+    }
+    .detect_key()
+    .normalize()
+    .clone())
+}
+
+fn text<'a>(node: Node<'a, '_>, name: &'static str) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)?
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// The lyrics without the leading space or verse number.
+fn strip_prefix(row: &str) -> &str {
+    row.strip_prefix(|c: char| c == ' ' || c.is_ascii_digit())
+        .unwrap_or(row)
+}
+
+fn parse_lyrics(lyrics: &str) -> Result<Vec<Section>, Error> {
+    let mut sections = Vec::default();
+    let mut title = String::default();
+    let mut lines = Vec::default();
+    let mut rows = lyrics.lines().map(str::trim_end).peekable();
+    while let Some(row) = rows.next() {
+        if let Some(marker) = row
+            .trim()
+            .strip_prefix('[')
+            .and_then(|row| row.strip_suffix(']'))
+        {
+            if !title.is_empty() || !lines.is_empty() {
+                sections.push(Section::new(title, std::mem::take(&mut lines)));
+            }
+            title = section_title(marker.trim(), OPEN_SONG_CODES);
+        } else if let Some(chord_line) = row.strip_prefix('.') {
+            let text = rows
+                .next_if(|next| !next.starts_with(['.', '[', ';']))
+                .map(strip_prefix)
+                .unwrap_or_default();
+            lines.push(Line::new(
+                ColumnIterator::new(lyrics, split_columns(chord_line), text)
+                    .collect::<Result<Vec<Part>, Error>>()?,
+            ));
+        } else if let Some(comment) = row.strip_prefix(';') {
+            lines.push(Comment::new(comment.trim().into(), CommentStyle::Normal).into());
+        } else if !row.trim().is_empty() && !row.starts_with('-') {
+            lines.push(Line::new(vec![("", strip_prefix(row)).try_into()?]));
+        }
+    }
+    if !title.is_empty() || !lines.is_empty() {
+        sections.push(Section::new(title, lines));
+    }
+    Ok(sections)
+}
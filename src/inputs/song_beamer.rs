@@ -0,0 +1,189 @@
+use super::iter_column::ColumnIterator;
+use crate::error::Error;
+use crate::types::{Arrangement, Key, Line, Metadata, Part, Section, SectionKind, Song};
+use base64::alphabet::STANDARD;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+
+/// Standard base64 that accepts the chords block with or without padding.
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// SongBeamer section names that are not a section kind, sections with them keep their title.
+static OTHER_NAMES: &[&str] = &["misc", "part", "teil", "unbekannt", "unbenannt"];
+
+pub fn load(path: &str) -> Result<Song, Error> {
+    load_string(&std::fs::read_to_string(path)?).map_err(|err| err.with_file(path))
+}
+
+/// Loads a SongBeamer song, the `#Key=Value` header is followed by slides separated by `---`.
+/// A slide starting with a section name like `Verse 1` or a name from `#VerseOrder` starts a
+/// section, other slides continue the previous one. With `#LangCount` greater than one the
+/// lyrics lines alternate between the languages and the `#Chords` block holds base64 encoded
+/// `column,line,chord` entries separated by `\r`, where the line counts the lines after the
+/// header.
+pub fn load_string(input: &str) -> Result<Song, Error> {
+    let input = input.trim_start_matches('\u{feff}');
+    let mut rows = input.lines();
+    let mut header = Vec::default();
+    for row in rows.by_ref() {
+        if row.trim_end() == "---" {
+            break;
+        }
+        if let Some((name, value)) = row.strip_prefix('#').and_then(|row| row.split_once('=')) {
+            header.push((name, value.trim()));
+        }
+    }
+    let field = |name: &str| {
+        header
+            .iter()
+            .find(|(other, _)| other.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut metadata = Metadata {
+        composer: field("Melody").map(String::from),
+        copyright: field("(c)").map(String::from),
+        ..Default::default()
+    };
+    if let Some(ccli) = field("CCLI") {
+        metadata.set("ccli", ccli)?;
+    }
+    if let Some(tempo) = field("Tempo").filter(|tempo| tempo.parse::<u16>().is_ok()) {
+        metadata.set("tempo", tempo)?;
+    }
+    let languages = match field("LangCount") {
+        Some(count) => count
+            .parse::<usize>()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or(Error::Parse(format!("invalid language count, {}", count)))?,
+        None => 1,
+    };
+    let chords = field("Chords")
+        .map(|chords| {
+            let chords = BASE64
+                .decode(chords.split_whitespace().collect::<String>())
+                .map_err(|err| Error::Parse(format!("invalid chords, {}", err)))?;
+            String::from_utf8(chords).map_err(|err| Error::Other(err.to_string()))
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let order = field("VerseOrder")
+        .map(|order| order.split(',').map(str::trim).collect::<Vec<&str>>())
+        .unwrap_or_default();
+    let arrangements = (!order.is_empty())
+        .then(|| {
+            Arrangement::from_sections(String::default(), order.iter().map(|name| name.to_string()))
+        })
+        .into_iter()
+        .collect();
+
+    Ok(Song {
+        title: field("Title")
+            .ok_or(Error::Parse("no title given".into()))?
+            .into(),
+        key: field("Key").map(Key::try_from).transpose()?,
+        artist: field("Author").map(String::from),
+        language: None,
+        capo: None,
+        instrument: None,
+        definitions: Vec::default(),
+        metadata,
+        arrangements,
+        sections: parse_slides(rows, languages, &chords, &order)?,
+    }
+    .detect_key()
+    .normalize()
+    .clone())
+}
+
+/// Whether the first line of a slide is a section name, custom names are only known from the
+/// verse order.
+fn is_section_name(row: &str, order: &[&str]) -> bool {
+    if order.contains(&row.trim()) {
+        return true;
+    }
+    let name = row
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .trim()
+        .to_lowercase();
+    SectionKind::parse(row).0 != SectionKind::Custom || OTHER_NAMES.contains(&name.as_str())
+}
+
+/// The `column,line,chord` entries of the decoded chords block.
+fn parse_chords(chords: &str) -> Result<Vec<(usize, usize, &str)>, Error> {
+    chords
+        .split(['\r', '\n'])
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let mut fields = entry.splitn(3, ',');
+            let mut number = || {
+                fields
+                    .next()
+                    .and_then(|number| number.trim().parse::<usize>().ok())
+                    .ok_or(Error::Parse(format!("invalid chord entry, {}", entry)))
+            };
+            let (column, line) = (number()?, number()?);
+            let chord = fields
+                .next()
+                .map(str::trim)
+                .filter(|chord| !chord.is_empty())
+                .ok_or(Error::Parse(format!("invalid chord entry, {}", entry)))?;
+            Ok((column, line, chord))
+        })
+        .collect()
+}
+
+fn parse_slides<'a>(
+    rows: impl Iterator<Item = &'a str>,
+    languages: usize,
+    chords: &str,
+    order: &[&str],
+) -> Result<Vec<Section>, Error> {
+    let entries = parse_chords(chords)?;
+    let mut sections = Vec::default();
+    let mut title = String::default();
+    let mut lines: Vec<Line> = Vec::default();
+    let mut slide_start = true;
+    let mut language = 0;
+    for (index, row) in rows.map(str::trim_end).enumerate() {
+        if row == "---" || row == "--" {
+            slide_start = true;
+            language = 0;
+            continue;
+        }
+        if std::mem::take(&mut slide_start) && is_section_name(row, order) {
+            if !title.is_empty() || !lines.is_empty() {
+                sections.push(Section::new(title, std::mem::take(&mut lines)));
+            }
+            title = row.trim().into();
+            continue;
+        }
+        let text = row.strip_prefix("##").unwrap_or(row);
+        if language > 0 {
+            if let Some(line) = lines.last_mut() {
+                line.add_translation(Line::new(vec![("", text).try_into()?]))?;
+            }
+        } else {
+            let mut columns = entries
+                .iter()
+                .filter(|(_, line, _)| *line == index)
+                .map(|(column, _, chord)| (*column, *chord))
+                .collect::<Vec<(usize, &str)>>();
+            columns.sort_by_key(|(column, _)| *column);
+            lines.push(Line::new(
+                ColumnIterator::new(chords, columns, text).collect::<Result<Vec<Part>, Error>>()?,
+            ));
+        }
+        language = (language + 1) % languages;
+    }
+    if !title.is_empty() || !lines.is_empty() {
+        sections.push(Section::new(title, lines));
+    }
+    Ok(sections)
+}
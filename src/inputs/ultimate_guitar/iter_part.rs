@@ -1,90 +1,63 @@
 use crate::error::Error;
+use crate::inputs::iter_column::ColumnIterator;
 use crate::types::Part;
 
+/// Aligns the text with the chord line above, written in `[ch]` markup.
 pub struct PartIterator<'a> {
-    content_chord: Option<&'a str>,
-    content_text: Option<&'a str>,
+    columns: Option<ColumnIterator<'a>>,
+    error: Option<Error>,
 }
 
 impl<'a> PartIterator<'a> {
-    pub fn new(content: &'a str) -> Self {
-        let vec = content.split('\n').collect::<Vec<&str>>();
-        if vec.len() > 1 {
-            Self {
-                content_chord: Some(vec[0]),
-                content_text: Some(vec[1]),
-            }
-        } else if vec.len() == 1 {
-            if vec[0].find("[ch]").is_some() {
-                Self {
-                    content_chord: Some(vec[0]),
-                    content_text: None,
-                }
-            } else {
-                Self {
-                    content_chord: None,
-                    content_text: Some(vec[0]),
-                }
-            }
-        } else {
-            Self {
-                content_chord: None,
-                content_text: None,
-            }
+    pub fn new(source: &'a str, content: &'a str) -> Self {
+        let mut lines = content.split('\n');
+        let (chord_line, text) = match (lines.next().unwrap_or_default(), lines.next()) {
+            (chord_line, Some(text)) => (chord_line, text),
+            (chord_line, None) if chord_line.contains("[ch]") => (chord_line, ""),
+            (text, None) => ("", text),
+        };
+
+        let mut chords = Vec::default();
+        let mut markup = 0;
+        let mut offset = 0;
+        while let Some(start) = chord_line[offset..]
+            .find("[ch]")
+            .map(|index| offset + index)
+        {
+            let Some(end) = chord_line[start + 4..]
+                .find("[/ch]")
+                .map(|index| start + 4 + index)
+            else {
+                let unclosed = &chord_line[start..];
+                return Self {
+                    columns: None,
+                    error: Some(
+                        Error::Parse(format!("unclosed chord, {}", unclosed)).at(source, unclosed),
+                    ),
+                };
+            };
+            chords.push((
+                chord_line[..start].chars().count() - markup,
+                &chord_line[start + 4..end],
+            ));
+            markup += 9;
+            offset = end + 5;
+        }
+        Self {
+            columns: Some(ColumnIterator::new(source, chords, text)),
+            error: None,
         }
     }
 }
 
-impl<'a> Iterator for PartIterator<'a> {
+impl Iterator for PartIterator<'_> {
     type Item = Result<Part, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(mut content_chord) = self.content_chord {
-            let old_content_chord = content_chord;
-            let start = content_chord.find("[ch]")?;
-            if start > 0 {
-                if let Some(content_text) = self.content_text {
-                    let byte_index = content_text
-                        .chars()
-                        .take(start)
-                        .map(|b| b.len_utf8())
-                        .sum::<usize>();
-                    let text = &content_text[..byte_index];
-                    self.content_text = Some(&content_text[byte_index..]);
-                    self.content_chord = Some(&content_chord[start..]);
-                    return Some(("", text).try_into());
-                }
-            }
-            content_chord = &content_chord[start + 4..];
-            let end = content_chord.find("[/ch]")?;
-            let chord = &content_chord[..end];
-            let next_start = content_chord.find("[ch]").unwrap_or(content_chord.len());
-            content_chord = &content_chord[next_start..];
-            self.content_chord = Some(content_chord);
-            if let Some(content_text) = self.content_text {
-                let text = if content_chord.len() > 0 {
-                    let char_index =
-                        old_content_chord.chars().count() - content_chord.chars().count() - 9;
-                    let byte_index = content_text
-                        .chars()
-                        .take(char_index)
-                        .map(|b| b.len_utf8())
-                        .sum::<usize>();
-                    let text = &content_text[..byte_index];
-                    self.content_text = Some(&content_text[byte_index..]);
-                    text
-                } else {
-                    let text = content_text;
-                    self.content_text = None;
-                    text
-                };
-                Some((chord, text).try_into())
-            } else {
-                Some((chord, "").try_into())
-            }
-        } else {
-            Some(("", self.content_text.take()?).try_into())
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
         }
+        self.columns.as_mut()?.next()
     }
 }
 
@@ -94,7 +67,7 @@ mod test {
 
     #[test]
     fn part_iterator() {
-        let inputs = vec![
+        let inputs = [
             "",
             "[ch]A[/ch]",
             "[ch]A[/ch] [ch]B[/ch]",
@@ -103,7 +76,7 @@ mod test {
             "    [ch]A[/ch]\nHey you",
             "Hello World",
         ];
-        let outputs = vec![
+        let outputs = [
             vec![("", "").try_into().unwrap()],
             vec![("A", "").try_into().unwrap()],
             vec![("A", "").try_into().unwrap(), ("B", "").try_into().unwrap()],
@@ -123,7 +96,7 @@ mod test {
         ];
         for (input, output) in inputs.iter().zip(outputs.iter()) {
             assert_eq!(
-                &PartIterator::new(input)
+                &PartIterator::new(input, input)
                     .collect::<Result<Vec<Part>, Error>>()
                     .unwrap(),
                 output
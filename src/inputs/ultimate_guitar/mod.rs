@@ -1,6 +1,6 @@
 use crate::error::Error;
 
-use crate::types::{Line, Part, Section, SimpleChord, Song};
+use crate::types::{Key, Line, Metadata, Part, Section, Song};
 
 mod iter_part;
 mod iter_section;
@@ -10,15 +10,19 @@ use iter_part::PartIterator;
 use iter_section::SectionIterator;
 use iter_tab::TabIterator;
 
-fn get_nested_field<'a>(json: &'a serde_json::Value, keys: &[&str]) -> Option<&'a str> {
+fn get_nested_value<'a>(
+    json: &'a serde_json::Value,
+    keys: &[&str],
+) -> Option<&'a serde_json::Value> {
     let mut current_value = json;
     for key in keys {
-        current_value = match current_value.get(key) {
-            Some(value) => value,
-            None => return None,
-        };
+        current_value = current_value.get(key)?;
     }
-    current_value.as_str()
+    Some(current_value)
+}
+
+fn get_nested_field<'a>(json: &'a serde_json::Value, keys: &[&str]) -> Option<&'a str> {
+    get_nested_value(json, keys)?.as_str()
 }
 
 #[cfg(feature = "download")]
@@ -42,42 +46,157 @@ pub fn load_html(html: &str) -> Result<Song, Error> {
         .value()
         .attr("data-content")
         .ok_or(Error::Parse("data-content not found".into()))?;
+    load_json(json)
+}
+
+/// Loads a tab from the JSON store of the page, with the tonality, capo and the tempo of the
+/// first strumming pattern where they are given.
+pub fn load_json(json: &str) -> Result<Song, Error> {
     let json: serde_json::Value = serde_json::from_str(json)?;
-    let content = get_nested_field(
-        &json,
-        &["store", "page", "data", "tab_view", "wiki_tab", "content"],
-    )
-    .ok_or(Error::Parse("content not found".into()))?
-    .replace("\r\n", "\n");
-    let title = get_nested_field(&json, &["store", "page", "data", "tab", "song_name"])
+    let data = get_nested_value(&json, &["store", "page", "data"])
+        .ok_or(Error::Parse("data not found".into()))?;
+    let content = get_nested_field(data, &["tab_view", "wiki_tab", "content"])
+        .ok_or(Error::Parse("content not found".into()))?
+        .replace("\r\n", "\n");
+    let title = get_nested_field(data, &["tab", "song_name"])
         .ok_or(Error::Parse("title not found".into()))?;
-    let artist = get_nested_field(&json, &["store", "page", "data", "tab", "artist_name"])
+    let artist = get_nested_field(data, &["tab", "artist_name"])
         .ok_or(Error::Parse("artist not found".into()))?;
-    load_string(&content, title, artist)
+
+    let tempo = get_nested_value(data, &["tab_view", "strummings"])
+        .and_then(|strummings| strummings.get(0)?.get("bpm")?.as_u64())
+        .and_then(|bpm| u16::try_from(bpm).ok())
+        .filter(|bpm| *bpm > 0);
+    let song = Song {
+        title: title.into(),
+        artist: Some(artist.into()),
+        key: [
+            &["tab", "tonality_name"][..],
+            &["tab_view", "meta", "tonality"],
+        ]
+        .into_iter()
+        .filter_map(|keys| get_nested_field(data, keys))
+        .find(|tonality| !tonality.trim().is_empty())
+        .map(Key::try_from)
+        .transpose()?,
+        capo: get_nested_value(data, &["tab_view", "meta", "capo"])
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|capo| u8::try_from(capo).ok())
+            .filter(|capo| *capo > 0),
+        metadata: Metadata {
+            tempo,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    load_sections(&content, song)
 }
 
 pub fn load_string(content: &str, title: &str, artist: &str) -> Result<Song, Error> {
-    let sections = SectionIterator::new(&content)
+    let song = Song {
+        title: title.into(),
+        artist: Some(artist.into()),
+        ..Default::default()
+    };
+    load_sections(content, song)
+}
+
+/// Adds the sections of the tab to the song, the chords are normalized to its key or the
+/// detected one.
+fn load_sections(content: &str, mut song: Song) -> Result<Song, Error> {
+    song.sections = SectionIterator::new(content)
         .map(|section| {
-            let index = section.find('\n').unwrap();
-            let title = section[1..index - 1].to_string();
+            let index = section
+                .find('\n')
+                .ok_or(Error::Parse(format!("no lines given, {}", section)).at(content, section))?;
+            let header = &section[..index];
+            let title = header
+                .trim_end()
+                .strip_prefix('[')
+                .and_then(|header| header.strip_suffix(']'))
+                .ok_or(
+                    Error::Parse(format!("invalid section header, {}", header)).at(content, header),
+                )?
+                .to_string();
             let lines = TabIterator::new(&section[index + 1..])
                 .map(|tab| {
-                    let parts = PartIterator::new(tab).collect::<Result<Vec<Part>, Error>>()?;
+                    let parts =
+                        PartIterator::new(content, tab).collect::<Result<Vec<Part>, Error>>()?;
                     Ok(Line::new(parts))
                 })
                 .collect::<Result<Vec<Line>, Error>>()?;
             Ok(Section::new(title, lines))
         })
         .collect::<Result<Vec<Section>, Error>>()?;
-    let key = SimpleChord::default();
-    Ok(Song {
-        title: title.into(),
-        key: Some(key),
-        artist: Some(artist.into()),
-        language: None, // TODO: parse language
-        sections,
+    // TODO: parse language
+    Ok(song.detect_key().normalize().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::{Chord, SectionKind};
+    use std::str::FromStr;
+
+    #[test]
+    fn load_errors() {
+        let content = "[Verse]\n[tab][ch]G[/ch]   [ch]Xyz[/ch]\nHello world[/tab]\n\n[Outro]\n";
+        match load_string(content, "Test", "Someone") {
+            Err(Error::Diagnostic(diagnostic)) => assert_eq!(
+                (diagnostic.line, diagnostic.column, diagnostic.length),
+                (2, 23, 3)
+            ),
+            result => panic!("expected a diagnostic, got {:?}", result),
+        }
+        assert!(load_string("[\nfoo\n[Verse]\n", "Test", "Someone").is_err());
+        assert!(load_string("[ä\nfoo\n[Verse]\n", "Test", "Someone").is_err());
+    }
+
+    #[test]
+    fn load_section_kinds() {
+        let content = "[Verse 1]\n[tab][ch]G[/ch]\nHello[/tab]\n\n[Refrain]\n[tab][ch]C[/ch]\nWorld[/tab]\n\n[Outro]\n";
+
+        let song = load_string(content, "Test", "Someone").unwrap();
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| (section.title.as_str(), section.kind, section.number))
+                .collect::<Vec<(&str, SectionKind, Option<u8>)>>(),
+            [
+                ("Verse 1", SectionKind::Verse, Some(1)),
+                ("Refrain", SectionKind::Chorus, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_json() {
+        let json = r#"{"store": {"page": {"data": {
+            "tab": {"song_name": "Amazing Grace", "artist_name": "John Newton", "tonality_name": "G"},
+            "tab_view": {
+                "meta": {"capo": 2, "tonality": "G"},
+                "strummings": [{"bpm": 72}],
+                "wiki_tab": {"content": "[Verse]\r\n[tab][ch]Em[/ch]\r\nHello[/tab]\r\n\r\n[Outro]\r\n"}
+            }
+        }}}}"#;
+
+        let song = super::load_json(json).unwrap();
+        assert_eq!(song.title, "Amazing Grace");
+        assert_eq!(song.key, Some(Key::try_from("G").unwrap()));
+        assert_eq!(song.capo, Some(2));
+        assert_eq!(song.metadata.tempo, Some(72));
+        assert_eq!(song.sections[0].title, "Verse");
+        assert_eq!(song.chords(), [Chord::from_str("Em").unwrap()]);
+
+        let song = super::load_json(
+            &json
+                .replace(r#""capo": 2"#, r#""capo": 0"#)
+                .replace(r#""tonality_name": "G""#, r#""tonality_name": """#)
+                .replace(r#"[{"bpm": 72}]"#, "[]"),
+        )
+        .unwrap();
+        assert_eq!((song.capo, song.metadata.tempo), (None, None));
+        assert_eq!(song.key, Some(Key::try_from("G").unwrap()));
+        assert!(super::load_json(r#"{"store": {}}"#).is_err());
     }
-    .normalize()
-    .clone())
 }
@@ -1,5 +1,5 @@
 mod error;
-pub use error::Error;
+pub use error::{Diagnostic, Error, Severity};
 
 pub mod inputs;
 pub mod outputs;
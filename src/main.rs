@@ -1,6 +1,9 @@
 use clap::Parser;
 
-use chordlib::outputs::{FormatChordPro, FormatRender};
+use chordlib::outputs::{
+    FormatChordPro, FormatHtml, FormatOpenLyrics, FormatOpenSong, FormatRender, FormatSongBeamer,
+    Notation, Pitch,
+};
 use chordlib::types::SimpleChord;
 use chordlib::Error;
 
@@ -17,15 +20,56 @@ struct Args {
     pub output: String,
     #[arg(short, long)]
     pub key: Option<u8>,
+    /// The chord notation (letter, sharps, flats, nashville or roman)
+    #[arg(short, long, default_value = "letter")]
+    pub notation: Notation,
+    /// The chord pitch with a capo (sounding or shapes)
+    #[arg(long, default_value = "sounding")]
+    pub pitch: Pitch,
+    /// The language of the lyrics, ChordPro output keeps all languages if not given
+    #[arg(short, long)]
+    pub language: Option<usize>,
+    /// Renders all languages below each other
+    #[arg(long, default_value_t = false)]
+    pub interlinear: bool,
+    /// Keeps going on errors in chordpro input and prints them as warnings
+    #[arg(long, default_value_t = false)]
+    pub lenient: bool,
+    /// Expands the song into the performed order of its first arrangement
+    #[arg(short, long, default_value_t = false)]
+    pub expand: bool,
+    /// Expands the song into the performed order of the named arrangement
+    #[arg(short, long)]
+    pub arrangement: Option<String>,
 }
 
-fn main() -> Result<(), Error> {
-    let args = Args::parse();
+fn main() {
+    if let Err(err) = run(Args::parse()) {
+        eprintln!("{}", err.pretty());
+        std::process::exit(1);
+    }
+}
 
+fn run(args: Args) -> Result<(), Error> {
     let mut song = if args.input.starts_with("https://tabs.ultimate-guitar.com/") {
         chordlib::inputs::ultimate_guitar::load_url(&args.input)
+    } else if args.input.ends_with(".cp") && args.lenient {
+        chordlib::inputs::chord_pro::load_lenient(&args.input).map(|(song, diagnostics)| {
+            for diagnostic in diagnostics {
+                eprintln!("{}", diagnostic.pretty());
+            }
+            song
+        })
     } else if args.input.ends_with(".cp") {
         chordlib::inputs::chord_pro::load(&args.input)
+    } else if args.input.ends_with(".txt") {
+        chordlib::inputs::chords_over_lyrics::load(&args.input)
+    } else if args.input.ends_with(".xml") {
+        chordlib::inputs::open_lyrics::load(&args.input)
+    } else if args.input.ends_with(".opensong") {
+        chordlib::inputs::open_song::load(&args.input)
+    } else if args.input.ends_with(".sng") {
+        chordlib::inputs::song_beamer::load(&args.input)
     } else {
         Err(Error::Other(format!(
             "unknown input format ({})",
@@ -37,18 +81,44 @@ fn main() -> Result<(), Error> {
         song.transpose(SimpleChord::new(key));
     }
 
+    if args.expand || args.arrangement.is_some() {
+        song = song.expand(args.arrangement.as_deref())?;
+    }
+
     if args.render {
-        println!("{}", song.format_render(None, None));
+        let render = if args.interlinear {
+            song.format_render_interlinear(None, args.notation, args.pitch)
+        } else {
+            song.format_render(None, args.language, args.notation, args.pitch)
+        };
+        println!("{}", render);
     }
 
     if args.output.ends_with(".cp") {
         Ok(std::fs::write(
             args.output,
-            (&song).format_chord_pro(None, None),
+            (&song).format_chord_pro(None, args.language, args.notation, args.pitch),
+        )?)
+    } else if args.output.ends_with(".xml") {
+        Ok(std::fs::write(
+            args.output,
+            (&song).format_open_lyrics(None),
+        )?)
+    } else if args.output.ends_with(".opensong") {
+        Ok(std::fs::write(args.output, (&song).format_open_song(None))?)
+    } else if args.output.ends_with(".sng") {
+        Ok(std::fs::write(
+            args.output,
+            (&song).format_song_beamer(None),
+        )?)
+    } else if args.output.ends_with(".html") {
+        Ok(std::fs::write(
+            args.output,
+            (&song).format_html_with_style(None, args.language, args.notation, args.pitch),
         )?)
     } else if args.output.ends_with(".json") {
         Ok(std::fs::write(args.output, serde_json::to_string(&song)?)?)
-    } else if args.output.len() == 0 {
+    } else if args.output.is_empty() {
         Ok(())
     } else {
         Err(Error::Other(format!(
@@ -1,43 +1,109 @@
-use crate::types::{Chord, Line, Part, Section, SimpleChord, Song};
+use super::{Notation, Pitch};
+use crate::types::{Chord, CommentStyle, Key, Line, Metadata, Part, Section, Song};
 
 pub trait FormatChordPro {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, language: Option<usize>) -> String;
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String;
 }
 
 impl FormatChordPro for &Chord {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, _: Option<usize>) -> String {
-        self.format(key.unwrap_or(SimpleChord::default()))
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        _: Option<usize>,
+        notation: Notation,
+        _: Pitch,
+    ) -> String {
+        notation.format_chord(self, &key.unwrap_or_default())
     }
 }
 
 impl FormatChordPro for &Part {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, language: Option<usize>) -> String {
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
         let chord = self
             .chord
-            .clone()
-            .map(|chord| format!("[{}]", (&chord).format_chord_pro(key.clone(), language)))
-            .unwrap_or("".into());
-        let language = language.unwrap_or(0);
-        format!("{}{}", chord, self.languages[language])
+            .as_ref()
+            .map(|chord| {
+                format!(
+                    "[{}]",
+                    chord.format_chord_pro(key, language, notation, pitch)
+                )
+            })
+            .or(self.raw_chord.as_ref().map(|chord| format!("[{}]", chord)))
+            .unwrap_or_default();
+        let text = self
+            .languages
+            .get(language.unwrap_or(0))
+            .or(self.languages.first())
+            .map(String::as_str)
+            .unwrap_or_default();
+        format!("{}{}", chord, text)
     }
 }
 
 impl FormatChordPro for &Line {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, language: Option<usize>) -> String {
-        self.parts
-            .iter()
-            .map(|part| part.format_chord_pro(key.clone(), language))
-            .collect()
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        if let Some(comment) = &self.comment {
+            return match comment.style {
+                CommentStyle::Normal => format!("{{comment: {}}}", comment.text),
+                CommentStyle::Italic => format!("{{comment_italic: {}}}", comment.text),
+                CommentStyle::Boxed => format!("{{comment_box: {}}}", comment.text),
+                CommentStyle::Hidden => format!("# {}", comment.text),
+            };
+        }
+        let format = |language: usize| {
+            self.parts
+                .iter()
+                .map(|part| part.format_chord_pro(key.clone(), Some(language), notation, pitch))
+                .collect::<String>()
+        };
+        // Without a language every translation follows as `{translation: ...}`.
+        match language {
+            Some(language) => format(language),
+            None => std::iter::once(format(0))
+                .chain(
+                    (1..self.languages())
+                        .map(|language| format!("{{translation: {}}}", format(language))),
+                )
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
     }
 }
 
 impl FormatChordPro for &Section {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, language: Option<usize>) -> String {
-        std::iter::once(format!("{{section: {}}}", self.title))
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        // lines before the first section have no title and no directive
+        (!self.title.is_empty())
+            .then(|| format!("{{section: {}}}", self.title))
+            .into_iter()
             .chain(
                 self.lines
                     .iter()
-                    .map(|line| line.format_chord_pro(key.clone(), language)),
+                    .map(|line| line.format_chord_pro(key.clone(), language, notation, pitch)),
             )
             .collect::<Vec<String>>()
             .join("\n")
@@ -45,12 +111,26 @@ impl FormatChordPro for &Section {
 }
 
 impl FormatChordPro for &Song {
-    fn format_chord_pro(&self, key: Option<SimpleChord>, language: Option<usize>) -> String {
-        let key = key.unwrap_or(self.key.clone().unwrap_or(SimpleChord::default()));
+    fn format_chord_pro(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        // the key is the one of the chords as written, the shape key when writing capo shapes
+        let written = pitch.key(&key, self.capo);
         let mut meta = vec![
             format!("{{title: {}}}", self.title),
-            format!("{{key: {}}}", SimpleChord::default().format(&key)),
+            format!("{{key: {}}}", notation.format_key(&written)),
         ];
+        if let Some(capo) = self.capo {
+            meta.push(format!("{{capo: {}}}", capo));
+        }
+        if let Some(instrument) = &self.instrument {
+            meta.push(format!("{{instrument: {}}}", instrument.format()));
+        }
         if let Some(artist) = self.artist.clone() {
             meta.push(format!("{{artist: {}}}", artist));
         }
@@ -58,12 +138,35 @@ impl FormatChordPro for &Song {
             meta.push(format!("{{language: {}}}", language));
         }
 
+        for (name, value) in self.metadata.entries() {
+            if Metadata::KEYS.contains(&name.as_str()) {
+                meta.push(format!("{{{}: {}}}", name, value));
+            } else {
+                meta.push(format!("{{meta: {} {}}}", name, value));
+            }
+        }
+
+        meta.extend(
+            self.arrangements
+                .iter()
+                .map(|arrangement| format!("{{arrangement: {}}}", arrangement.format())),
+        );
+
+        let key = written;
+        // the fingerings are only valid for the chords as written in the song
+        if self.key.as_ref().map(Key::tonic) == Some(key.tonic()) {
+            meta.extend(self.definitions.iter().map(|(chord, fingering)| {
+                format!(
+                    "{{define: {} {}}}",
+                    chord.clone().normalize(key.tonic()).format(&key),
+                    fingering.format_define()
+                )
+            }));
+        }
         meta.into_iter()
-            .chain(
-                self.sections
-                    .iter()
-                    .map(|section| section.format_chord_pro(Some(key.clone()), language)),
-            )
+            .chain(self.sections.iter().map(|section| {
+                section.format_chord_pro(Some(key.clone()), language, notation, pitch)
+            }))
             .collect::<Vec<String>>()
             .join("\n")
     }
@@ -0,0 +1,227 @@
+use super::xml::escape;
+use super::{Notation, Pitch};
+use crate::types::{CommentStyle, Key, Line, Section, SectionKind, Song};
+
+/// Stacks the chords above the text of their part and keeps the spaces of the lyrics.
+static STYLESHEET: &str = "<style>
+.song .metadata dt { float: left; clear: left; margin-right: 0.5em; font-weight: bold; }
+.song .metadata dd { margin: 0; }
+.song .section > h2 { font-size: 1em; color: #b22222; }
+.song .line { margin: 0; }
+.song .part { display: inline-block; vertical-align: bottom; white-space: pre; }
+.song .chord, .song .text, .song .translation { display: block; }
+.song .chord { min-height: 1.2em; font-weight: bold; color: #228b22; }
+.song .translation { font-style: italic; color: #4682b4; }
+.song .comment { font-style: italic; color: #b8860b; }
+.song .comment.boxed { display: inline-block; border: 1px solid; padding: 0 0.25em; }
+</style>";
+
+pub trait FormatHtml {
+    fn format_html(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String;
+
+    /// The markup preceded by the default stylesheet.
+    fn format_html_with_style(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        format!(
+            "{}\n{}",
+            STYLESHEET,
+            self.format_html(key, language, notation, pitch)
+        )
+    }
+}
+
+impl FormatHtml for &Line {
+    fn format_html(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        _: Pitch,
+    ) -> String {
+        if let Some(comment) = &self.comment {
+            let style = match comment.style {
+                CommentStyle::Normal => "",
+                CommentStyle::Italic => " italic",
+                CommentStyle::Boxed => " boxed",
+                CommentStyle::Hidden => return String::default(),
+            };
+            return format!(
+                "<p class=\"comment{}\">{}</p>",
+                style,
+                escape(&comment.text)
+            );
+        }
+        // Without a language every language is written below the other.
+        let languages = match language {
+            Some(language) => vec![language],
+            None => (0..self.languages().max(1)).collect(),
+        };
+        let has_chords = self
+            .parts
+            .iter()
+            .any(|part| part.chord.is_some() || part.raw_chord.is_some());
+        let key = key.unwrap_or_default();
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| {
+                let mut spans = String::default();
+                if has_chords {
+                    let chord = part
+                        .chord
+                        .as_ref()
+                        .map(|chord| notation.format_chord(chord, &key))
+                        .or(part.raw_chord.clone())
+                        .unwrap_or_default();
+                    spans.push_str(&format!("<span class=\"chord\">{}</span>", escape(&chord)));
+                }
+                for (index, language) in languages.iter().enumerate() {
+                    let text = part
+                        .languages
+                        .get(*language)
+                        .or(part.languages.first())
+                        .map(String::as_str)
+                        .unwrap_or_default();
+                    let class = if index == 0 { "text" } else { "translation" };
+                    spans.push_str(&format!(
+                        "<span class=\"{}\">{}</span>",
+                        class,
+                        escape(text)
+                    ));
+                }
+                format!("<span class=\"part\">{}</span>", spans)
+            })
+            .collect::<String>();
+        format!("<p class=\"line\">{}</p>", parts)
+    }
+}
+
+impl FormatHtml for &Section {
+    fn format_html(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        let kind = match self.kind {
+            SectionKind::Custom => "custom".to_string(),
+            kind => kind.format().to_lowercase(),
+        };
+        std::iter::once(format!("<section class=\"section {}\">", kind))
+            .chain((!self.title.is_empty()).then(|| format!("<h2>{}</h2>", escape(&self.title))))
+            .chain(
+                self.lines
+                    .iter()
+                    .map(|line| line.format_html(key.clone(), language, notation, pitch))
+                    .filter(|line| !line.is_empty()),
+            )
+            .chain(std::iter::once("</section>".to_string()))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl FormatHtml for &Song {
+    fn format_html(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        let mut header = vec![format!("<h1 class=\"title\">{}</h1>", escape(&self.title))];
+        if let Some(artist) = &self.artist {
+            header.push(format!("<p class=\"artist\">{}</p>", escape(artist)));
+        }
+        let mut metadata = vec![("key".to_string(), notation.format_key(&key))];
+        if let Some(capo) = self.capo {
+            metadata.push(("capo".into(), capo.to_string()));
+        }
+        metadata.extend(self.metadata.entries());
+        metadata.extend(
+            self.arrangements
+                .iter()
+                .map(|arrangement| ("arrangement".into(), arrangement.format())),
+        );
+        header.push(format!(
+            "<dl class=\"metadata\">{}</dl>",
+            metadata
+                .iter()
+                .map(|(name, value)| format!("<dt>{}</dt><dd>{}</dd>", escape(name), escape(value)))
+                .collect::<String>()
+        ));
+
+        let key = pitch.key(&key, self.capo);
+        std::iter::once("<article class=\"song\">".to_string())
+            .chain(std::iter::once(format!(
+                "<header>\n{}\n</header>",
+                header.join("\n")
+            )))
+            .chain(
+                self.sections.iter().map(|section| {
+                    section.format_html(Some(key.clone()), language, notation, pitch)
+                }),
+            )
+            .chain(std::iter::once("</article>".to_string()))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::chord_pro::load_string;
+
+    #[test]
+    fn format_html() {
+        let song = load_string(
+            "{title: Amazing Grace}
+{artist: John Newton}
+{key: G}
+{section: Verse 1}
+Amazing [G]grace
+{translation: O Gnade Gottes}
+{comment_box: slowly}
+{section: Outro}
+Amen & amen",
+        )
+        .unwrap();
+
+        assert_eq!(
+            (&song).format_html(None, None, Notation::default(), Pitch::default()),
+            "<article class=\"song\">
+<header>
+<h1 class=\"title\">Amazing Grace</h1>
+<p class=\"artist\">John Newton</p>
+<dl class=\"metadata\"><dt>key</dt><dd>G</dd></dl>
+</header>
+<section class=\"section verse\">
+<h2>Verse 1</h2>
+<p class=\"line\"><span class=\"part\"><span class=\"chord\"></span><span class=\"text\">Amazing </span><span class=\"translation\">O Gnade Gottes</span></span><span class=\"part\"><span class=\"chord\">G</span><span class=\"text\">grace</span><span class=\"translation\"></span></span></p>
+<p class=\"comment boxed\">slowly</p>
+</section>
+<section class=\"section outro\">
+<h2>Outro</h2>
+<p class=\"line\"><span class=\"part\"><span class=\"text\">Amen &amp; amen</span></span></p>
+</section>
+</article>"
+        );
+        assert!((&song.sections[1])
+            .format_html_with_style(None, Some(0), Notation::default(), Pitch::default())
+            .starts_with("<style>"));
+    }
+}
@@ -1,7 +1,23 @@
 mod chord_pro;
+mod html;
+mod notation;
+mod open_lyrics;
+mod open_song;
 mod outputline;
+mod pitch;
 mod render;
+mod song_beamer;
+mod verse_order;
+mod voicing;
+mod xml;
 
 pub use chord_pro::FormatChordPro;
+pub use html::FormatHtml;
+pub use notation::Notation;
+pub use open_lyrics::FormatOpenLyrics;
+pub use open_song::FormatOpenSong;
 pub use outputline::{FormatOutputLines, OutputLine};
+pub use pitch::Pitch;
 pub use render::FormatRender;
+pub use song_beamer::FormatSongBeamer;
+pub use voicing::FormatVoicings;
@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::types::{Chord, Key, Spelling};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Notation {
+    Letter(Spelling),
+    Nashville,
+    Roman,
+}
+
+impl Default for Notation {
+    fn default() -> Self {
+        Notation::Letter(Spelling::default())
+    }
+}
+
+impl Notation {
+    pub fn format_chord(&self, chord: &Chord, key: &Key) -> String {
+        match self {
+            Notation::Letter(spelling) => chord.format_spelled(key, *spelling),
+            Notation::Nashville => chord.format_nashville(),
+            Notation::Roman => chord.format_roman(key.mode()),
+        }
+    }
+
+    pub fn format_key(&self, key: &Key) -> String {
+        match self {
+            Notation::Letter(spelling) => key.format_spelled(*spelling),
+            _ => key.format(),
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "letter" => Ok(Notation::Letter(Spelling::Theoretical)),
+            "sharps" => Ok(Notation::Letter(Spelling::Sharps)),
+            "flats" => Ok(Notation::Letter(Spelling::Flats)),
+            "nashville" => Ok(Notation::Nashville),
+            "roman" => Ok(Notation::Roman),
+            _ => Err(Error::Parse(format!("unknown notation, {}", s))),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use super::verse_order::VerseOrder;
+use super::xml::escape;
+use super::Notation;
+use crate::types::{section_code, Key, Line, Song, OPEN_LYRICS_CODES};
+
+pub trait FormatOpenLyrics {
+    fn format_open_lyrics(&self, key: Option<Key>) -> String;
+}
+
+impl FormatOpenLyrics for &Song {
+    /// Writes an OpenLyrics document, repeated sections are written once and the first
+    /// arrangement becomes the verse order. Translations are written as verses of the language
+    /// codes of the song, languages without a code get a private use code like `x-lang2`. Sections
+    /// of other kinds are named by their title, e.g. `My_Part`, untitled ones `o`.
+    fn format_open_lyrics(&self, key: Option<Key>) -> String {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        let mut properties = vec![format!(
+            "    <titles>\n      <title>{}</title>\n    </titles>",
+            escape(&self.title)
+        )];
+        let authors = [
+            (None, self.artist.as_ref()),
+            (Some("music"), self.metadata.composer.as_ref()),
+            (Some("words"), self.metadata.lyricist.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(kind, name)| {
+            let kind = kind
+                .map(|kind| format!(" type=\"{}\"", kind))
+                .unwrap_or_default();
+            Some(format!("      <author{}>{}</author>", kind, escape(name?)))
+        })
+        .collect::<Vec<String>>();
+        if !authors.is_empty() {
+            properties.push(format!(
+                "    <authors>\n{}\n    </authors>",
+                authors.join("\n")
+            ));
+        }
+        if let Some(copyright) = &self.metadata.copyright {
+            properties.push(format!("    <copyright>{}</copyright>", escape(copyright)));
+        }
+        if let Some(ccli) = self.metadata.ccli {
+            properties.push(format!("    <ccliNo>{}</ccliNo>", ccli));
+        }
+        if let Some(year) = self.metadata.year {
+            properties.push(format!("    <released>{}</released>", year));
+        }
+        if self.key.is_some() {
+            properties.push(format!("    <key>{}</key>", key.format()));
+        }
+        if let Some(tempo) = self.metadata.tempo {
+            properties.push(format!("    <tempo type=\"bpm\">{}</tempo>", tempo));
+        }
+
+        let count = self
+            .sections
+            .iter()
+            .flat_map(|section| section.lines.iter().map(Line::languages))
+            .max()
+            .unwrap_or_default();
+        let codes = self.language_codes();
+        let langs = (0..count.max(1))
+            .map(|language| match codes.get(language) {
+                Some(code) if !code.is_empty() => format!(" lang=\"{}\"", escape(code)),
+                Some(_) => String::default(),
+                None if count > 1 => format!(" lang=\"x-lang{}\"", language + 1),
+                None => String::default(),
+            })
+            .collect::<Vec<String>>();
+
+        let verse_order = VerseOrder::new(self, |section| {
+            match section_code(section, OPEN_LYRICS_CODES) {
+                code if code.is_empty() => "o".into(),
+                code => code,
+            }
+        });
+        let mut verses = Vec::default();
+        for (section, name) in &verse_order.sections {
+            let count = section
+                .lines
+                .iter()
+                .map(Line::languages)
+                .max()
+                .unwrap_or_default()
+                .max(1);
+            for (language, lang) in langs.iter().enumerate().take(count) {
+                let lines = section
+                    .lines
+                    .iter()
+                    .map(|line| format_line(line, language, &key))
+                    .collect::<Vec<String>>()
+                    .join("<br/>");
+                verses.push(format!(
+                    "    <verse name=\"{}\"{}>\n      <lines>{}</lines>\n    </verse>",
+                    escape(name),
+                    lang,
+                    lines
+                ));
+            }
+        }
+        let order = verse_order.order;
+        if !order.is_empty() {
+            properties.push(format!(
+                "    <verseOrder>{}</verseOrder>",
+                escape(&order.join(" "))
+            ));
+        }
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<song xmlns=\"http://openlyrics.info/namespace/2009/song\" version=\"0.8\" createdIn=\"chordlib\" modifiedIn=\"chordlib\">\n  <properties>\n{}\n  </properties>\n  <lyrics>\n{}\n  </lyrics>\n</song>\n",
+            properties.join("\n"),
+            verses.join("\n")
+        )
+    }
+}
+
+fn format_line(line: &Line, language: usize, key: &Key) -> String {
+    if let Some(comment) = &line.comment {
+        return format!("<comment>{}</comment>", escape(&comment.text));
+    }
+    line.parts
+        .iter()
+        .map(|part| {
+            let chord = part
+                .chord
+                .as_ref()
+                .map(|chord| Notation::default().format_chord(chord, key))
+                .or(part.raw_chord.clone())
+                .map(|chord| format!("<chord name=\"{}\"/>", escape(&chord)))
+                .unwrap_or_default();
+            let text = part
+                .languages
+                .get(language)
+                .or(part.languages.first())
+                .map(|text| escape(text))
+                .unwrap_or_default();
+            format!("{}{}", chord, text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::open_lyrics::load_string;
+
+    #[test]
+    fn open_lyrics_round_trip() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<song xmlns="http://openlyrics.info/namespace/2009/song" version="0.8" createdIn="chordlib" modifiedIn="chordlib">
+  <properties>
+    <titles>
+      <title>Amazing Grace</title>
+    </titles>
+    <authors>
+      <author>John Newton</author>
+      <author type="words">John Newton</author>
+    </authors>
+    <ccliNo>22025</ccliNo>
+    <released>1779</released>
+    <key>G</key>
+    <tempo type="bpm">72</tempo>
+    <verseOrder>v1 c c v2</verseOrder>
+  </properties>
+  <lyrics>
+    <verse name="v1" lang="en">
+      <lines>Amazing <chord name="G"/>grace, how <chord name="G7"/>sweet<br/><chord name="C"/>the <chord name="G"/>sound</lines>
+    </verse>
+    <verse name="v1" lang="de">
+      <lines>O Gnade <chord name="G"/>Gottes, <chord name="G7"/>wunderbar<br/><chord name="C"/>hast du <chord name="G"/>errettet</lines>
+    </verse>
+    <verse name="c" lang="en">
+      <lines><chord name="Dsus4"/>Hallelujah &amp; <chord name="Em7"/>amen<br/><comment>repeat</comment></lines>
+    </verse>
+    <verse name="v2" lang="en">
+      <lines><chord name="Cmaj7"/>Twas <chord name="D/F#"/>grace</lines>
+    </verse>
+  </lyrics>
+</song>
+"#;
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.language, Some("en, de".into()));
+        assert_eq!(song.metadata.ccli, Some(22025));
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| section.title.as_str())
+                .collect::<Vec<&str>>(),
+            ["Verse 1", "Chorus", "Verse 2"]
+        );
+        assert_eq!(song.arrangements[0].format(), "Verse 1, Chorus x2, Verse 2");
+        assert_eq!(
+            song.sections[0].lines[1].parts[0].languages,
+            ["the ", "hast du "]
+        );
+        assert_eq!((&song).format_open_lyrics(None), input);
+        assert_eq!(
+            load_string(&(&song).format_open_lyrics(None)).unwrap(),
+            song
+        );
+    }
+
+    #[test]
+    fn open_lyrics_translations_without_codes() {
+        let song = crate::inputs::chord_pro::load_string(
+            "{title: Test}\n{key: C}\n{section: Verse}\nDu [Am7]bist [G/B]treu\n{translation: You are faithful}",
+        )
+        .unwrap();
+
+        let output = (&song).format_open_lyrics(None);
+        assert!(output.contains("<verse name=\"v\" lang=\"x-lang2\">"));
+        let loaded = load_string(&output).unwrap();
+        assert_eq!(loaded.language, Some("x-lang1, x-lang2".into()));
+        assert_eq!(loaded.sections, song.sections);
+    }
+
+    #[test]
+    fn open_lyrics_custom_sections() {
+        let song = crate::inputs::chord_pro::load_string(
+            "{title: Test}
+{key: C}
+{arrangement: Verse, Tag, My Part, Instrumental, My Part}
+{section: Verse}
+[C]Hello
+{section: Tag}
+[G]world
+{section: My Part}
+[F]again
+{section: Instrumental}
+[C]",
+        )
+        .unwrap();
+
+        let output = (&song).format_open_lyrics(None);
+        assert!(output.contains("<verseOrder>v Tag My_Part Instrumental My_Part</verseOrder>"));
+        let loaded = load_string(&output).unwrap();
+        assert_eq!(loaded.sections, song.sections);
+        assert_eq!(
+            loaded.arrangements[0].format(),
+            song.arrangements[0].format()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use super::verse_order::VerseOrder;
+use super::xml::escape;
+use super::Notation;
+use crate::types::{section_code, Key, Line, Song, OPEN_SONG_CODES};
+
+pub trait FormatOpenSong {
+    fn format_open_song(&self, key: Option<Key>) -> String;
+}
+
+impl FormatOpenSong for &Song {
+    /// Writes an OpenSong document, repeated sections are written once and the first arrangement
+    /// becomes the presentation order. Sections of other kinds are marked by their title with
+    /// underscores for spaces, e.g. `[My_Part]`.
+    fn format_open_song(&self, key: Option<Key>) -> String {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        let mut fields = vec![("title", Some(self.title.clone()))];
+        fields.push(("author", self.artist.clone()));
+        fields.push(("copyright", self.metadata.copyright.clone()));
+        fields.push(("ccli", self.metadata.ccli.map(|ccli| ccli.to_string())));
+        fields.push(("capo", self.capo.map(|capo| capo.to_string())));
+        fields.push(("key", self.key.as_ref().map(|_| key.format())));
+        fields.push(("tempo", self.metadata.tempo.map(|tempo| tempo.to_string())));
+        fields.push(("time_sig", self.metadata.time.clone()));
+
+        let verse_order = VerseOrder::new(self, |section| section_code(section, OPEN_SONG_CODES));
+        let mut lyrics = Vec::default();
+        for (section, name) in &verse_order.sections {
+            if !section.title.is_empty() {
+                lyrics.push(format!("[{}]", name));
+            }
+            lyrics.extend(
+                section
+                    .lines
+                    .iter()
+                    .flat_map(|line| format_line(line, &key)),
+            );
+        }
+        let order = verse_order.order;
+        if !order.is_empty() {
+            fields.push(("presentation", Some(order.join(" "))));
+        }
+        fields.push(("lyrics", Some(lyrics.join("\n"))));
+
+        let fields = fields
+            .into_iter()
+            .filter_map(|(name, value)| Some(format!("  <{0}>{1}</{0}>", name, escape(&value?))))
+            .collect::<Vec<String>>()
+            .join("\n");
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<song>\n{}\n</song>\n",
+            fields
+        )
+    }
+}
+
+/// Writes the chords in a line starting with `.` above the lyrics starting with a space, the
+/// lyrics are padded to keep the chords at their columns.
+fn format_line(line: &Line, key: &Key) -> Vec<String> {
+    if let Some(comment) = &line.comment {
+        return vec![format!(";{}", comment.text)];
+    }
+    let mut chord_line = String::default();
+    let mut text_line = String::default();
+    for part in &line.parts {
+        let chord = part
+            .chord
+            .as_ref()
+            .map(|chord| Notation::default().format_chord(chord, key))
+            .or(part.raw_chord.clone());
+        if let Some(chord) = chord {
+            let chord_chars = chord_line.chars().count();
+            let text_chars = text_line.chars().count();
+            let column = match chord_chars {
+                0 => text_chars,
+                _ => text_chars.max(chord_chars + 1),
+            };
+            chord_line.push_str(&" ".repeat(column - chord_chars));
+            text_line.push_str(&" ".repeat(column - text_chars));
+            chord_line.push_str(&chord);
+        }
+        text_line.push_str(part.languages.first().map_or("", String::as_str));
+    }
+
+    let mut result = Vec::default();
+    if !chord_line.is_empty() {
+        result.push(format!(".{}", chord_line));
+    }
+    let text_line = text_line.trim_end();
+    if !text_line.is_empty() || chord_line.is_empty() {
+        result.push(format!(" {}", text_line));
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::open_song::load_string;
+
+    #[test]
+    fn open_song_round_trip() {
+        let input = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<song>
+  <title>Amazing Grace</title>
+  <author>John Newton</author>
+  <ccli>22025</ccli>
+  <capo>2</capo>
+  <key>G</key>
+  <time_sig>3/4</time_sig>
+  <presentation>V1 C C V2</presentation>
+  <lyrics>[V1]
+.        G          G7
+ Amazing grace, how sweet
+.C   G
+ the sound
+;slowly
+[C]
+.D  Em
+ Hallelujah &amp; amen
+[V2]
+ Twas grace</lyrics>
+</song>
+";
+
+        let song = load_string(input).unwrap();
+        assert_eq!(song.capo, Some(2));
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| section.title.as_str())
+                .collect::<Vec<&str>>(),
+            ["Verse 1", "Chorus", "Verse 2"]
+        );
+        assert_eq!(
+            song.sections[0].lines[0].parts[1].languages,
+            ["grace, how "]
+        );
+        assert_eq!(song.arrangements[0].format(), "Verse 1, Chorus x2, Verse 2");
+        assert_eq!((&song).format_open_song(None), input);
+        assert_eq!(load_string(&(&song).format_open_song(None)).unwrap(), song);
+    }
+
+    #[test]
+    fn open_song_custom_sections() {
+        let song = crate::inputs::chord_pro::load_string(
+            "{title: Test}
+{key: C}
+{arrangement: Verse, My Part, Verse}
+{section: Verse}
+[C]Hello
+{section: My Part}
+[G]world",
+        )
+        .unwrap();
+
+        let output = (&song).format_open_song(None);
+        assert!(output.contains("<presentation>V My_Part V</presentation>"));
+        let loaded = load_string(&output).unwrap();
+        assert_eq!(loaded.sections, song.sections);
+        assert_eq!(loaded.arrangements, song.arrangements);
+        assert!(loaded.expand(None).is_ok());
+    }
+}
@@ -1,86 +1,252 @@
-use crate::types::{Line, Section, SimpleChord, Song};
+use super::{Notation, Pitch};
+use crate::types::{CommentStyle, Key, Line, Section, Song};
 
 pub enum OutputLine {
     Keyword(String),
     Chord(String),
     Text(String),
+    Comment(String),
+    /// The lyrics in another language below the text.
+    Translation(String),
 }
 
 pub trait FormatOutputLines {
     fn format_output_lines(
         &self,
-        key: Option<SimpleChord>,
+        key: Option<Key>,
         language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> Vec<OutputLine>;
+
+    /// Writes every language below the primary lyrics, aligned on the chords.
+    fn format_output_lines_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        pitch: Pitch,
     ) -> Vec<OutputLine>;
 }
 
 impl FormatOutputLines for &Line {
     fn format_output_lines(
         &self,
-        key: Option<SimpleChord>,
+        key: Option<Key>,
         language: Option<usize>,
+        notation: Notation,
+        _: Pitch,
     ) -> Vec<OutputLine> {
-        let mut chord_line = String::default();
-        let mut text_line = String::default();
-        let language = language.unwrap_or(0);
-
-        for part in &self.parts {
-            if let Some(chord) = part.chord.clone() {
-                let chord_chars = chord_line.chars().count();
-                let text_chars = text_line.chars().count();
-                if text_chars > chord_chars {
-                    for _ in 0..(text_chars - chord_chars) {
-                        chord_line.push_str(" ");
-                    }
-                } else if chord_chars > 0 {
-                    chord_line.push_str(" ");
-                }
-                chord_line = format!(
-                    "{}{}",
-                    chord_line,
-                    chord.format(key.clone().unwrap_or(SimpleChord::default()))
-                );
-            }
-            text_line = format!("{}{}", text_line, part.languages[language]);
-        }
+        format_line(self, key, &[language.unwrap_or(0)], notation)
+    }
 
-        let mut result = Vec::default();
-        if chord_line.len() > 0 {
-            result.push(OutputLine::Chord(chord_line));
-        }
-        if text_line.len() > 0 {
-            result.push(OutputLine::Text(text_line));
-        }
-        result
+    fn format_output_lines_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        _: Pitch,
+    ) -> Vec<OutputLine> {
+        let languages = (0..self.languages().max(1)).collect::<Vec<usize>>();
+        format_line(self, key, &languages, notation)
     }
 }
 
 impl FormatOutputLines for &Section {
     fn format_output_lines(
         &self,
-        key: Option<SimpleChord>,
+        key: Option<Key>,
         language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
     ) -> Vec<OutputLine> {
-        std::iter::once(OutputLine::Keyword(self.title.clone()))
-            .chain(
-                self.lines
-                    .iter()
-                    .flat_map(|line| line.format_output_lines(key.clone(), language)),
-            )
-            .collect()
+        format_section(self, |line| {
+            line.format_output_lines(key.clone(), language, notation, pitch)
+        })
+    }
+
+    fn format_output_lines_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> Vec<OutputLine> {
+        format_section(self, |line| {
+            line.format_output_lines_interlinear(key.clone(), notation, pitch)
+        })
     }
 }
 
 impl FormatOutputLines for &Song {
     fn format_output_lines(
         &self,
-        key: Option<SimpleChord>,
+        key: Option<Key>,
         language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> Vec<OutputLine> {
+        format_song(self, key, pitch, |section, key| {
+            section.format_output_lines(Some(key), language, notation, pitch)
+        })
+    }
+
+    fn format_output_lines_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        pitch: Pitch,
     ) -> Vec<OutputLine> {
-        let key = key.unwrap_or(self.key.clone().unwrap_or(SimpleChord::default()));
-        self.sections
-            .iter()
-            .flat_map(|section| section.format_output_lines(Some(key.clone()), language))
-            .collect()
+        format_song(self, key, pitch, |section, key| {
+            section.format_output_lines_interlinear(Some(key), notation, pitch)
+        })
+    }
+}
+
+/// Writes the chords above the lyrics of the first language and the other languages below,
+/// all lyrics are aligned on the chords.
+fn format_line(
+    line: &Line,
+    key: Option<Key>,
+    languages: &[usize],
+    notation: Notation,
+) -> Vec<OutputLine> {
+    if let Some(comment) = &line.comment {
+        return match comment.style {
+            CommentStyle::Hidden => Vec::default(),
+            _ => vec![OutputLine::Comment(comment.text.clone())],
+        };
+    }
+    let mut chord_line = String::default();
+    let mut text_lines = vec![String::default(); languages.len()];
+    let key = key.unwrap_or_default();
+
+    for part in &line.parts {
+        let chord = part
+            .chord
+            .as_ref()
+            .map(|chord| notation.format_chord(chord, &key))
+            .or(part.raw_chord.clone());
+        if let Some(chord) = chord {
+            let chord_chars = chord_line.chars().count();
+            let text_chars = text_lines
+                .iter()
+                .map(|text_line| text_line.chars().count())
+                .max()
+                .unwrap_or_default();
+            for text_line in &mut text_lines {
+                let chars = text_line.chars().count();
+                text_line.push_str(&" ".repeat(text_chars - chars));
+            }
+            if text_chars > chord_chars {
+                chord_line.push_str(&" ".repeat(text_chars - chord_chars));
+            } else if chord_chars > 0 {
+                chord_line.push(' ');
+            }
+            chord_line.push_str(&chord);
+        }
+        for (text_line, language) in text_lines.iter_mut().zip(languages) {
+            if let Some(text) = part.languages.get(*language).or(part.languages.first()) {
+                text_line.push_str(text);
+            }
+        }
+    }
+
+    let mut result = Vec::default();
+    if !chord_line.is_empty() {
+        result.push(OutputLine::Chord(chord_line));
+    }
+    let mut text_lines = text_lines.into_iter();
+    if let Some(text_line) = text_lines.next().filter(|text_line| !text_line.is_empty()) {
+        result.push(OutputLine::Text(text_line));
+    }
+    result.extend(
+        text_lines
+            .map(|text_line| text_line.trim_end().to_string())
+            .filter(|text_line| !text_line.is_empty())
+            .map(OutputLine::Translation),
+    );
+    result
+}
+
+fn format_section(
+    section: &Section,
+    format_line: impl Fn(&Line) -> Vec<OutputLine>,
+) -> Vec<OutputLine> {
+    std::iter::once(OutputLine::Keyword(section.title.clone()))
+        .chain(section.lines.iter().flat_map(format_line))
+        .collect()
+}
+
+fn format_song(
+    song: &Song,
+    key: Option<Key>,
+    pitch: Pitch,
+    format_section: impl Fn(&Section, Key) -> Vec<OutputLine>,
+) -> Vec<OutputLine> {
+    let key = pitch.key(
+        &key.unwrap_or(song.key.clone().unwrap_or_default()),
+        song.capo,
+    );
+    song.arrangements
+        .iter()
+        .map(|arrangement| OutputLine::Comment(arrangement.format()))
+        .chain(
+            song.sections
+                .iter()
+                .flat_map(|section| format_section(section, key.clone())),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::chord_pro::load_string;
+
+    #[test]
+    fn format_interlinear() {
+        let song = load_string(
+            "{title: Test}\n{key: C}\n{section: Verse}\nDu [C]bist [G]treu\n{translation: You [C]are [G]faithful}",
+        )
+        .unwrap();
+        let format = |lines: Vec<OutputLine>| {
+            lines
+                .into_iter()
+                .map(|line| match line {
+                    OutputLine::Translation(text) => format!("> {}", text),
+                    OutputLine::Keyword(text)
+                    | OutputLine::Chord(text)
+                    | OutputLine::Text(text)
+                    | OutputLine::Comment(text) => text,
+                })
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            format((&song).format_output_lines_interlinear(
+                None,
+                Notation::default(),
+                Pitch::default()
+            )),
+            [
+                "Verse",
+                "    C    G",
+                "Du  bist treu",
+                "> You are  faithful"
+            ]
+        );
+        let format_language = |language: Option<usize>| {
+            format((&song).format_output_lines(
+                None,
+                language,
+                Notation::default(),
+                Pitch::default(),
+            ))
+        };
+        assert_eq!(
+            format_language(None),
+            ["Verse", "   C    G", "Du bist treu"]
+        );
+        assert_eq!(
+            format_language(Some(1)),
+            ["Verse", "    C   G", "You are faithful"]
+        );
     }
 }
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::types::Key;
+
+/// Whether chords are written as they sound or as they are fingered behind a capo.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Pitch {
+    #[default]
+    Sounding,
+    Shapes,
+}
+
+impl Pitch {
+    /// The key the chords are written in for a song in the given key.
+    pub fn key(&self, key: &Key, capo: Option<u8>) -> Key {
+        match (self, capo) {
+            (Pitch::Shapes, Some(capo)) => key.shape(capo),
+            _ => key.clone(),
+        }
+    }
+}
+
+impl FromStr for Pitch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sounding" | "concert" => Ok(Pitch::Sounding),
+            "shapes" => Ok(Pitch::Shapes),
+            _ => Err(Error::Parse(format!("unknown pitch, {}", s))),
+        }
+    }
+}
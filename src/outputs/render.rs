@@ -1,20 +1,55 @@
-use super::{FormatOutputLines, OutputLine};
-use crate::types::{SimpleChord, Song};
+use super::{FormatOutputLines, Notation, OutputLine, Pitch};
+use crate::types::{Key, Song};
 
 pub trait FormatRender {
-    fn format_render(&self, key: Option<SimpleChord>, language: Option<usize>) -> String;
+    fn format_render(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String;
+
+    /// Renders every language below the primary lyrics.
+    fn format_render_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String;
 }
 
 impl FormatRender for Song {
-    fn format_render(&self, key: Option<SimpleChord>, language: Option<usize>) -> String {
-        self.format_output_lines(key, language)
-            .iter()
-            .map(|line| match line {
-                OutputLine::Keyword(keyword) => format!("\x1b[31;1m{}\x1b[0m", keyword),
-                OutputLine::Chord(chord) => format!("\x1b[32;1m{}\x1b[0m", chord),
-                OutputLine::Text(text) => format!("\x1b[32m{}\x1b[0m", text),
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+    fn format_render(
+        &self,
+        key: Option<Key>,
+        language: Option<usize>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        render(self.format_output_lines(key, language, notation, pitch))
+    }
+
+    fn format_render_interlinear(
+        &self,
+        key: Option<Key>,
+        notation: Notation,
+        pitch: Pitch,
+    ) -> String {
+        render(self.format_output_lines_interlinear(key, notation, pitch))
     }
 }
+
+fn render(lines: Vec<OutputLine>) -> String {
+    lines
+        .iter()
+        .map(|line| match line {
+            OutputLine::Keyword(keyword) => format!("\x1b[31;1m{}\x1b[0m", keyword),
+            OutputLine::Chord(chord) => format!("\x1b[32;1m{}\x1b[0m", chord),
+            OutputLine::Text(text) => format!("\x1b[32m{}\x1b[0m", text),
+            OutputLine::Comment(comment) => format!("\x1b[33;3m{}\x1b[0m", comment),
+            OutputLine::Translation(text) => format!("\x1b[36;3m{}\x1b[0m", text),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
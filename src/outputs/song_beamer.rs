@@ -0,0 +1,191 @@
+use super::verse_order::VerseOrder;
+use super::Notation;
+use crate::types::{Key, Line, Song};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+pub trait FormatSongBeamer {
+    fn format_song_beamer(&self, key: Option<Key>) -> String;
+}
+
+impl FormatSongBeamer for &Song {
+    /// Writes a SongBeamer song with one slide per section, repeated sections are written once
+    /// and the first arrangement becomes the verse order. Sections keep their title, which is
+    /// also listed in the verse order so custom names are read back, untitled sections are
+    /// named `Misc`. SongBeamer has no comments, so comment lines are lost.
+    fn format_song_beamer(&self, key: Option<Key>) -> String {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        let languages = self
+            .sections
+            .iter()
+            .flat_map(|section| section.lines.iter().map(Line::languages))
+            .max()
+            .unwrap_or_default()
+            .max(1);
+
+        let verse_order = VerseOrder::new(self, |section| {
+            if section.title.trim().is_empty() {
+                "Misc".into()
+            } else {
+                section.title.trim().into()
+            }
+        });
+        let mut body = Vec::default();
+        let mut chords = String::default();
+        for (section, name) in &verse_order.sections {
+            if !body.is_empty() {
+                body.push("---".to_string());
+            }
+            body.push(name.clone());
+            for line in section.lines.iter().filter(|line| line.comment.is_none()) {
+                let mut text = String::default();
+                for part in &line.parts {
+                    let chord = part
+                        .chord
+                        .as_ref()
+                        .map(|chord| Notation::default().format_chord(chord, &key))
+                        .or(part.raw_chord.clone());
+                    if let Some(chord) = chord {
+                        chords.push_str(&format!(
+                            "{},{},{}\r",
+                            text.chars().count(),
+                            body.len(),
+                            chord
+                        ));
+                    }
+                    text.push_str(part.languages.first().map_or("", String::as_str));
+                }
+                body.push(text);
+                for language in 1..languages {
+                    body.push(
+                        line.parts
+                            .iter()
+                            .map(|part| part.languages.get(language).map_or("", String::as_str))
+                            .collect(),
+                    );
+                }
+            }
+        }
+        let order = if verse_order.order.is_empty() {
+            verse_order
+                .sections
+                .iter()
+                .map(|(_, name)| name.clone())
+                .collect()
+        } else {
+            verse_order.order
+        };
+
+        let mut header = vec![
+            ("LangCount", Some(languages.to_string())),
+            ("Title", Some(self.title.clone())),
+            ("Author", self.artist.clone()),
+            ("Melody", self.metadata.composer.clone()),
+            ("(c)", self.metadata.copyright.clone()),
+            ("CCLI", self.metadata.ccli.map(|ccli| ccli.to_string())),
+            ("Key", self.key.as_ref().map(|_| key.format())),
+            ("Tempo", self.metadata.tempo.map(|tempo| tempo.to_string())),
+        ];
+        if !order.is_empty() {
+            header.push(("VerseOrder", Some(order.join(","))));
+        }
+        if !chords.is_empty() {
+            header.push(("Chords", Some(BASE64.encode(chords.as_bytes()))));
+        }
+
+        header
+            .into_iter()
+            .filter_map(|(name, value)| Some(format!("#{}={}", name, value?)))
+            .chain(std::iter::once("---".to_string()))
+            .chain(body)
+            .chain(std::iter::once(String::default()))
+            .collect::<Vec<String>>()
+            .join("\r\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::song_beamer::load_string;
+
+    #[test]
+    fn song_beamer_round_trip() {
+        let chords = "8,1,G\r19,1,G7\r0,3,C\r4,3,G\r0,7,D\r13,7,Em\r0,11,G\r";
+        let input = format!(
+            "#LangCount=2
+#Title=Amazing Grace
+#Author=John Newton
+#CCLI=22025
+#Key=G
+#VerseOrder=Verse 1,Chorus,Chorus,Verse 2
+#Chords={}
+---
+Verse 1
+Amazing grace, how sweet
+O Gnade Gottes, wunderbar
+the sound
+hast du errettet
+---
+Chorus
+Hallelujah & amen
+Halleluja & amen
+---
+Verse 2
+Twas grace
+Es war Gnade
+",
+            BASE64.encode(chords.as_bytes())
+        )
+        .replace('\n', "\r\n");
+
+        let song = load_string(&input).unwrap();
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| section.title.as_str())
+                .collect::<Vec<&str>>(),
+            ["Verse 1", "Chorus", "Verse 2"]
+        );
+        assert_eq!(
+            song.sections[0].lines[0].parts[1].languages,
+            ["grace, how ", ""]
+        );
+        assert_eq!(
+            song.sections[0].lines[1].parts[0].languages,
+            ["the ", "hast du errettet"]
+        );
+        assert_eq!(song.arrangements[0].format(), "Verse 1, Chorus x2, Verse 2");
+        assert_eq!((&song).format_song_beamer(None), input);
+        assert_eq!(
+            load_string(&(&song).format_song_beamer(None)).unwrap(),
+            song
+        );
+    }
+
+    #[test]
+    fn song_beamer_custom_titles() {
+        let song = crate::inputs::chord_pro::load_string(
+            "{title: Amazing Grace}
+{section: Verse 1}
+Amazing [G]grace
+{section: Tag}
+{comment: slowly}
+how sweet the [C]sound",
+        )
+        .unwrap();
+        let output = (&song).format_song_beamer(None);
+        assert!(output.contains("#VerseOrder=Verse 1,Tag\r\n"));
+        assert!(!output.contains("slowly"));
+
+        let song = load_string(&output).unwrap();
+        assert_eq!(
+            song.sections
+                .iter()
+                .map(|section| section.title.as_str())
+                .collect::<Vec<&str>>(),
+            ["Verse 1", "Tag"]
+        );
+        assert_eq!(song.sections[1].lines.len(), 1);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::types::{Section, Song};
+
+/// The distinct sections of a song with unique names and the order they are performed in, for
+/// formats that write every section once and refer to it by name.
+pub struct VerseOrder<'a> {
+    /// The sections in the order of the song, repeated sections only once.
+    pub sections: Vec<(&'a Section, String)>,
+    /// The names in the order of the first arrangement, or of the song if sections repeat. It is
+    /// empty if every section is performed once in the order of the song.
+    pub order: Vec<String>,
+}
+
+impl<'a> VerseOrder<'a> {
+    /// Names every distinct section, a name that is taken gets the next free number instead of
+    /// its own, e.g. `v2` for a second `v1`.
+    pub fn new(song: &'a Song, name: impl Fn(&Section) -> String) -> Self {
+        let mut sections: Vec<(&Section, String)> = Vec::default();
+        let mut order = Vec::default();
+        for section in &song.sections {
+            if let Some((_, name)) = sections
+                .iter()
+                .find(|(other, _)| other.title == section.title)
+            {
+                order.push(name.clone());
+                continue;
+            }
+            let mut name = name(section);
+            let stem = name
+                .trim_end_matches(|c: char| c.is_ascii_digit())
+                .to_string();
+            let mut number = 1;
+            while sections.iter().any(|(_, other)| *other == name) {
+                number += 1;
+                name = format!("{}{}", stem, number);
+            }
+            order.push(name.clone());
+            sections.push((section, name));
+        }
+
+        if let Some(arrangement) = song.arrangements.first() {
+            order = arrangement
+                .steps
+                .iter()
+                .filter_map(|step| {
+                    let (_, name) = sections
+                        .iter()
+                        .find(|(section, _)| section.title == step.section)?;
+                    Some(vec![name.clone(); step.repeat as usize])
+                })
+                .flatten()
+                .collect();
+        } else if order.len() == sections.len() {
+            order.clear();
+        }
+        Self { sections, order }
+    }
+}
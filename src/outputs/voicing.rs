@@ -0,0 +1,79 @@
+use super::{Notation, OutputLine};
+use crate::types::{Key, Line, Section, Song};
+
+pub trait FormatVoicings {
+    fn format_voicings(&self, key: Option<Key>, notation: Notation) -> Vec<OutputLine>;
+}
+
+impl FormatVoicings for &Line {
+    fn format_voicings(&self, key: Option<Key>, notation: Notation) -> Vec<OutputLine> {
+        let key = key.unwrap_or_default();
+        self.parts
+            .iter()
+            .filter_map(|part| part.chord.as_ref())
+            .zip(self.voicings(&key))
+            .map(|(chord, voicing)| {
+                OutputLine::Chord(format!(
+                    "{}: {}",
+                    notation.format_chord(chord, &key),
+                    voicing.format()
+                ))
+            })
+            .collect()
+    }
+}
+
+impl FormatVoicings for &Section {
+    fn format_voicings(&self, key: Option<Key>, notation: Notation) -> Vec<OutputLine> {
+        std::iter::once(OutputLine::Keyword(self.title.clone()))
+            .chain(
+                self.lines
+                    .iter()
+                    .flat_map(|line| line.format_voicings(key.clone(), notation)),
+            )
+            .collect()
+    }
+}
+
+impl FormatVoicings for &Song {
+    fn format_voicings(&self, key: Option<Key>, notation: Notation) -> Vec<OutputLine> {
+        let key = key.unwrap_or(self.key.clone().unwrap_or_default());
+        self.sections
+            .iter()
+            .flat_map(|section| section.format_voicings(Some(key.clone()), notation))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::inputs::chord_pro::load_string;
+
+    #[test]
+    fn format_voicings() {
+        let song =
+            load_string("{title: Test}\n{key: C}\n{section: Verse}\n[C]Hello [F]dear [G/B]world")
+                .unwrap();
+        let lines = (&song)
+            .format_voicings(None, Notation::default())
+            .into_iter()
+            .map(|line| match line {
+                OutputLine::Keyword(text)
+                | OutputLine::Chord(text)
+                | OutputLine::Text(text)
+                | OutputLine::Comment(text)
+                | OutputLine::Translation(text) => text,
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(
+            lines,
+            [
+                "Verse",
+                "C: C2 G2 | C4 E4 G4",
+                "F: F2 C3 | C4 F4 A4",
+                "G/B: B2 | B3 D4 G4"
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// A section of the song played one or more times in a row.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct ArrangementStep {
+    /// The title of the referenced section.
+    pub section: String,
+    pub repeat: u8,
+}
+
+impl ArrangementStep {
+    pub fn new(section: String, repeat: u8) -> Self {
+        Self { section, repeat }
+    }
+
+    pub fn format(&self) -> String {
+        if self.repeat == 1 {
+            self.section.clone()
+        } else {
+            format!("{} x{}", self.section, self.repeat)
+        }
+    }
+}
+
+impl TryFrom<&str> for ArrangementStep {
+    type Error = Error;
+
+    /// Parses a section title with an optional repeat count, e.g. `Chorus x2`. A last word that
+    /// is not `x` followed by a count of at least one is part of the title.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let s = s.trim();
+        let (section, repeat) = s
+            .rsplit_once(char::is_whitespace)
+            .and_then(|(section, repeat)| {
+                let repeat = repeat.strip_prefix(['x', 'X'])?.parse::<u8>().ok()?;
+                (repeat > 0).then_some((section.trim(), repeat))
+            })
+            .unwrap_or((s, 1));
+        if section.is_empty() {
+            return Err(Error::Parse("no section given".into()));
+        }
+        Ok(Self::new(section.into(), repeat))
+    }
+}
+
+/// The order in which the sections of a song are performed.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Arrangement {
+    pub name: String,
+    pub steps: Vec<ArrangementStep>,
+}
+
+impl Arrangement {
+    pub fn new(name: String, steps: Vec<ArrangementStep>) -> Self {
+        Self { name, steps }
+    }
+
+    /// Collects the titles of the performed sections, a section played several times in a row
+    /// becomes one step.
+    pub fn from_sections(name: String, sections: impl IntoIterator<Item = String>) -> Self {
+        let mut steps: Vec<ArrangementStep> = Vec::default();
+        for section in sections {
+            match steps.last_mut() {
+                Some(step) if step.section == section && step.repeat < u8::MAX => step.repeat += 1,
+                _ => steps.push(ArrangementStep::new(section, 1)),
+            }
+        }
+        Self::new(name, steps)
+    }
+
+    /// Writes the steps separated by commas, prefixed by the name if there is one, e.g.
+    /// `Live: Verse 1, Chorus x2`.
+    pub fn format(&self) -> String {
+        let steps = self
+            .steps
+            .iter()
+            .map(ArrangementStep::format)
+            .collect::<Vec<String>>()
+            .join(", ");
+        if self.name.is_empty() {
+            steps
+        } else {
+            format!("{}: {}", self.name, steps)
+        }
+    }
+}
+
+impl TryFrom<&str> for Arrangement {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (name, steps) = s.split_once(':').unwrap_or(("", s));
+        let steps = steps
+            .split(',')
+            .map(ArrangementStep::try_from)
+            .collect::<Result<Vec<ArrangementStep>, Error>>()?;
+        Ok(Self::new(name.trim().into(), steps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arrangement_from_str() {
+        let arrangement = Arrangement::try_from("Live: Verse 1, Chorus, Bridge x2").unwrap();
+        assert_eq!(arrangement.name, "Live");
+        assert_eq!(
+            arrangement.steps,
+            [
+                ArrangementStep::new("Verse 1".into(), 1),
+                ArrangementStep::new("Chorus".into(), 1),
+                ArrangementStep::new("Bridge".into(), 2),
+            ]
+        );
+        assert_eq!(arrangement.format(), "Live: Verse 1, Chorus, Bridge x2");
+        assert_eq!(
+            Arrangement::try_from("Verse, Chorus X3").unwrap().format(),
+            "Verse, Chorus x3"
+        );
+        assert!(Arrangement::try_from("Verse, , Chorus").is_err());
+        assert_eq!(
+            Arrangement::try_from("Chorus xylophone, Tag x0")
+                .unwrap()
+                .steps,
+            [
+                ArrangementStep::new("Chorus xylophone".into(), 1),
+                ArrangementStep::new("Tag x0".into(), 1),
+            ]
+        );
+        let arrangement = Arrangement::from_sections(String::default(), vec!["Chorus".into(); 300]);
+        assert_eq!(arrangement.format(), "Chorus x255, Chorus x45");
+    }
+}
@@ -1,9 +1,66 @@
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::SimpleChord;
+use super::{
+    Added, Alteration, ChordTone, Extension, Interval, Key, Mode, Note, Omission, Seventh,
+    SimpleChord, Spelling,
+};
 use crate::error::Error;
 
+type RootParser = fn(&str) -> Result<(SimpleChord, &str), Error>;
+
+static ROMAN_NUMERALS: &[&str] = &["I", "II", "III", "IV", "V", "VI", "VII"];
+
+static MAJOR_SEVENTH_PREFIXES: &[&str] = &["maj", "Maj", "MAJ", "ma", "M", "Δ", "^"];
+
+static KIND_PREFIXES: &[(&str, Kind)] = &[
+    ("dim", Kind::Diminished),
+    ("°", Kind::Diminished),
+    ("aug", Kind::Augmented),
+    ("+", Kind::Augmented),
+    ("min", Kind::Minor),
+    ("mi", Kind::Minor),
+    ("m", Kind::Minor),
+    ("-", Kind::Minor),
+];
+
+static ALTERATION_PREFIXES: &[(&str, Alteration)] = &[
+    ("b5", Alteration::FlatFifth),
+    ("-5", Alteration::FlatFifth),
+    ("♭5", Alteration::FlatFifth),
+    ("#5", Alteration::SharpFifth),
+    ("+5", Alteration::SharpFifth),
+    ("♯5", Alteration::SharpFifth),
+    ("b9", Alteration::FlatNinth),
+    ("-9", Alteration::FlatNinth),
+    ("♭9", Alteration::FlatNinth),
+    ("#9", Alteration::SharpNinth),
+    ("+9", Alteration::SharpNinth),
+    ("♯9", Alteration::SharpNinth),
+    ("#11", Alteration::SharpEleventh),
+    ("+11", Alteration::SharpEleventh),
+    ("♯11", Alteration::SharpEleventh),
+    ("b13", Alteration::FlatThirteenth),
+    ("-13", Alteration::FlatThirteenth),
+    ("♭13", Alteration::FlatThirteenth),
+];
+
+static ADDED_PREFIXES: &[(&str, Added)] = &[
+    ("13", Added::Thirteenth),
+    ("11", Added::Eleventh),
+    ("9", Added::Ninth),
+    ("6", Added::Sixth),
+    ("4", Added::Fourth),
+    ("2", Added::Second),
+];
+
+static OMISSION_PREFIXES: &[(&str, Omission)] = &[
+    ("no3", Omission::Third),
+    ("no5", Omission::Fifth),
+    ("omit3", Omission::Third),
+    ("omit5", Omission::Fifth),
+];
+
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub enum Kind {
     #[default]
@@ -13,6 +70,7 @@ pub enum Kind {
     Augmented,
     Suspended2,
     Suspended4,
+    Power,
 }
 
 impl Kind {
@@ -24,6 +82,47 @@ impl Kind {
             Kind::Augmented => "aug",
             Kind::Suspended2 => "sus2",
             Kind::Suspended4 => "sus4",
+            Kind::Power => "5",
+        }
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        matches!(self, Kind::Suspended2 | Kind::Suspended4)
+    }
+
+    pub fn intervals(&self) -> Vec<Interval> {
+        match self {
+            Kind::Major => vec![
+                Interval::new(1, 0),
+                Interval::new(3, 0),
+                Interval::new(5, 0),
+            ],
+            Kind::Minor => vec![
+                Interval::new(1, 0),
+                Interval::new(3, -1),
+                Interval::new(5, 0),
+            ],
+            Kind::Diminished => vec![
+                Interval::new(1, 0),
+                Interval::new(3, -1),
+                Interval::new(5, -1),
+            ],
+            Kind::Augmented => vec![
+                Interval::new(1, 0),
+                Interval::new(3, 0),
+                Interval::new(5, 1),
+            ],
+            Kind::Suspended2 => vec![
+                Interval::new(1, 0),
+                Interval::new(2, 0),
+                Interval::new(5, 0),
+            ],
+            Kind::Suspended4 => vec![
+                Interval::new(1, 0),
+                Interval::new(4, 0),
+                Interval::new(5, 0),
+            ],
+            Kind::Power => vec![Interval::new(1, 0), Interval::new(5, 0)],
         }
     }
 }
@@ -33,6 +132,16 @@ pub struct Chord {
     main: SimpleChord,
     base: Option<SimpleChord>,
     kind: Kind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seventh: Option<Seventh>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extension: Option<Extension>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    added: Vec<Added>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    alterations: Vec<Alteration>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    omissions: Vec<Omission>,
     var: String,
 }
 
@@ -41,17 +150,34 @@ impl Chord {
         Self::default().transpose(level)
     }
 
-    pub fn transpose(self: Self, level: u8) -> Self {
+    pub fn root(&self) -> &SimpleChord {
+        &self.main
+    }
+
+    pub fn bass(&self) -> Option<&SimpleChord> {
+        self.base.as_ref()
+    }
+
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// The part of the name that was not understood, e.g. `47` of `Cm47`.
+    pub fn unparsed(&self) -> &str {
+        &self.var
+    }
+
+    pub fn transpose(self, level: u8) -> Self {
         let mut result = self;
         result.main = result.main.transpose(level);
-        result.base.clone().map(|base| base.transpose(level));
+        result.base = result.base.map(|base| base.transpose(level));
         result
     }
 
-    pub fn normalize(self: Self, key: &SimpleChord) -> Self {
+    pub fn normalize(self, key: &SimpleChord) -> Self {
         let mut result = self;
-        result.main = result.main.normalize(&key);
-        result.base = result.base.clone().map(|base| base.normalize(&key));
+        result.main = result.main.normalize(key);
+        result.base = result.base.map(|base| base.normalize(key));
         result
     }
 
@@ -91,114 +217,552 @@ impl Chord {
         result
     }
 
+    pub fn power(self) -> Self {
+        let mut result = self;
+        result.kind = Kind::Power;
+        result
+    }
+
     pub fn base(self, base: SimpleChord) -> Self {
         let mut result = self;
         result.base = Some(base);
         result
     }
 
+    pub fn seventh(self, seventh: Seventh) -> Self {
+        let mut result = self;
+        result.seventh = Some(seventh);
+        result
+    }
+
+    /// Sets the highest extension, an extension implies a (minor) seventh if none is set yet.
+    pub fn extension(self, extension: Extension) -> Self {
+        let mut result = self;
+        result.seventh.get_or_insert(Seventh::Minor);
+        result.extension = Some(extension);
+        result
+    }
+
+    pub fn added(self, added: Added) -> Self {
+        let mut result = self;
+        insert_sorted(&mut result.added, added);
+        result
+    }
+
+    pub fn alter(self, alteration: Alteration) -> Self {
+        let mut result = self;
+        insert_sorted(&mut result.alterations, alteration);
+        result
+    }
+
+    pub fn omit(self, omission: Omission) -> Self {
+        let mut result = self;
+        insert_sorted(&mut result.omissions, omission);
+        result
+    }
+
     pub fn var(self, var: String) -> Self {
         let mut result = self;
         result.var = var;
         result
     }
 
-    pub fn format(&self, key: SimpleChord) -> String {
+    /// The intervals of all chord tones above the root, ordered by degree.
+    pub fn intervals(&self) -> Vec<Interval> {
+        let mut intervals = self.kind.intervals();
+        intervals.extend(self.seventh.iter().map(|seventh| seventh.interval()));
+        if let Some(extension) = &self.extension {
+            intervals.extend(extension.intervals(self.kind == Kind::Minor));
+        }
+        intervals.extend(self.added.iter().map(|added| added.interval()));
+        for alteration in &self.alterations {
+            let interval = alteration.interval();
+            intervals
+                .retain(|other| other.degree() != interval.degree() || other.alteration() != 0);
+            intervals.push(interval);
+        }
+        intervals.retain(|interval| {
+            !self
+                .omissions
+                .iter()
+                .any(|omission| omission.degree() == interval.degree())
+        });
+        intervals.sort();
+        intervals.dedup();
+        intervals
+    }
+
+    /// The tones of the chord with their absolute pitch and a note name spelled from the root.
+    pub fn tones(&self, key: &Key) -> Vec<ChordTone> {
+        let root = Spelling::default().spell(&self.main, key);
+        let main = self.main.transpose(key.tonic().level());
+        self.intervals()
+            .into_iter()
+            .map(|interval| ChordTone {
+                pitch: main.transpose(interval.semitones()),
+                note: root.transpose(&interval),
+                interval,
+            })
+            .collect()
+    }
+
+    /// The distinct pitch classes sounding in the chord (including the bass), sorted by level.
+    pub fn pitch_classes(&self, key: &Key) -> Vec<SimpleChord> {
+        let mut pitch_classes = self
+            .tones(key)
+            .into_iter()
+            .map(|tone| tone.pitch)
+            .chain(
+                self.base
+                    .iter()
+                    .map(|base| base.transpose(key.tonic().level())),
+            )
+            .collect::<Vec<SimpleChord>>();
+        pitch_classes.sort();
+        pitch_classes.dedup();
+        pitch_classes
+    }
+
+    pub fn format(&self, key: &Key) -> String {
+        self.format_spelled(key, Spelling::default())
+    }
+
+    pub fn format_spelled(&self, key: &Key, spelling: Spelling) -> String {
         format!(
-            "{}{}{}{}",
-            self.main.format(&key),
-            self.kind.format(),
+            "{}{}{}",
+            self.main.format_spelled(key, spelling),
+            self.format_suffix(),
             self.base
-                .clone()
-                .map(|base| format!("/{}", base.format(&key)))
-                .unwrap_or("".into()),
-            self.var
+                .as_ref()
+                .map(|base| format!("/{}", base.format_spelled(key, spelling)))
+                .unwrap_or_default(),
         )
     }
 
-    fn parse_simple_chord<'a>(s: &'a str) -> Result<(SimpleChord, &'a str), Error> {
-        let l1 = s.chars().next().map_or(0, |c| c.len_utf8());
-        let l2 = s.chars().nth(1).map_or(0, |c| c.len_utf8());
+    /// Formats the chord in the Nashville Number System, this expects a normalized chord.
+    pub fn format_nashville(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.main.format_nashville(),
+            self.format_suffix(),
+            self.base
+                .as_ref()
+                .map(|base| format!("/{}", base.format_nashville()))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Parses a chord in the Nashville Number System, the result is normalized.
+    pub fn from_nashville(s: &str) -> Result<Self, Error> {
+        Self::parse(s, Self::parse_nashville_number)
+    }
+
+    /// Formats the chord as Roman numeral for harmonic analysis relative to the scale of the
+    /// mode, this expects a normalized chord. Dominant seventh chords resolving to a diatonic
+    /// degree other than the tonic are written as secondary dominants (`V7/V`), inversions use
+    /// figured bass.
+    pub fn format_roman(&self, mode: Mode) -> String {
+        let half_diminished = self.kind == Kind::Minor
+            && self.seventh == Some(Seventh::Minor)
+            && self.alterations.contains(&Alteration::FlatFifth);
+        let lowercase = matches!(self.kind, Kind::Minor | Kind::Diminished);
+        let quality = match self.kind {
+            Kind::Diminished => "°",
+            Kind::Augmented => "+",
+            _ if half_diminished => "ø",
+            _ => "",
+        };
+        let figure = self.format_roman_figure();
+
+        let target = (self.main.level() + 5) % 12;
+        let secondary =
+            if self.kind == Kind::Major && self.seventh == Some(Seventh::Minor) && target != 0 {
+                diatonic_roman_numeral(target, mode)
+            } else {
+                None
+            };
+        let numeral = if let Some(secondary) = secondary {
+            format!("V{}/{}", figure, secondary)
+        } else {
+            let numeral = roman_numeral(self.main.level(), mode);
+            format!(
+                "{}{}{}",
+                if lowercase {
+                    numeral.to_lowercase()
+                } else {
+                    numeral.to_string()
+                },
+                quality,
+                figure
+            )
+        };
+
+        let mut result = numeral;
+        if self.kind.is_suspended() || self.kind == Kind::Power {
+            result.push_str(self.kind.format());
+        }
+        for added in &self.added {
+            result.push_str(&format!("add{}", added.format()));
+        }
+        for alteration in &self.alterations {
+            if !(half_diminished && *alteration == Alteration::FlatFifth) {
+                result.push_str(alteration.format());
+            }
+        }
+        for omission in &self.omissions {
+            result.push_str(omission.format());
+        }
+        result.push_str(&self.var);
+        if let Some(base) = &self.base {
+            if figure_for_bass(self, base).is_none() {
+                result.push_str(&format!("/{}", base.format_nashville()));
+            }
+        }
+        result
+    }
+
+    /// The seventh/extension or the figured bass of an inverted chord.
+    fn format_roman_figure(&self) -> String {
+        let major = if self.seventh == Some(Seventh::Major) {
+            "M"
+        } else {
+            ""
+        };
+        if let Some(figure) = self
+            .base
+            .as_ref()
+            .and_then(|base| figure_for_bass(self, base))
+        {
+            return format!("{}{}", major, figure);
+        }
+        match (&self.seventh, &self.extension) {
+            (Some(_), Some(extension)) => format!("{}{}", major, extension.format()),
+            (Some(_), None) => format!("{}7", major),
+            (None, _) => String::default(),
+        }
+    }
+
+    fn format_suffix(&self) -> String {
+        let mut result = String::default();
+        if !self.kind.is_suspended() {
+            result.push_str(self.kind.format());
+        }
+
+        let number = self
+            .extension
+            .as_ref()
+            .map(|extension| extension.format())
+            .unwrap_or("7");
+        match self.seventh {
+            Some(Seventh::Major) => result.push_str(&format!("maj{}", number)),
+            Some(Seventh::Minor) => result.push_str(number),
+            Some(Seventh::Diminished) if self.kind == Kind::Diminished => result.push('7'),
+            Some(Seventh::Diminished) => result.push_str("dim7"),
+            None => (),
+        }
+
+        let mut added = self.added.iter().collect::<Vec<&Added>>();
+        if self.seventh.is_none() && self.added.contains(&Added::Sixth) {
+            added.retain(|added| **added != Added::Sixth);
+            result.push('6');
+            if self.added.contains(&Added::Ninth) {
+                added.retain(|added| **added != Added::Ninth);
+                result.push_str("/9");
+            }
+        }
+
+        if self.kind.is_suspended() {
+            result.push_str(self.kind.format());
+        }
+        for added in added {
+            result.push_str(&format!("add{}", added.format()));
+        }
+        for alteration in &self.alterations {
+            result.push_str(alteration.format());
+        }
+        for omission in &self.omissions {
+            result.push_str(omission.format());
+        }
+        result.push_str(&self.var);
+        result
+    }
+
+    fn parse_simple_chord(s: &str) -> Result<(SimpleChord, &str), Error> {
+        Note::parse(s)
+            .map(|(note, s)| (SimpleChord::new(note.level()), s))
+            .map_err(|_| Error::Parse("can not parse a simple chord from an empty string".into()))
+    }
+
+    fn parse_nashville_number(s: &str) -> Result<(SimpleChord, &str), Error> {
+        let (accidental, rest) = match s.chars().next() {
+            Some(c @ ('b' | '♭')) => (-1, &s[c.len_utf8()..]),
+            Some(c @ ('#' | '♯')) => (1, &s[c.len_utf8()..]),
+            _ => (0, s),
+        };
+        match rest.chars().next().and_then(|c| c.to_digit(10)) {
+            Some(degree @ 1..=7) => Ok((
+                SimpleChord::new(Interval::new(degree as u8, accidental).semitones()),
+                &rest[1..],
+            )),
+            _ => Err(Error::Parse(format!(
+                "can not parse a nashville number from {}",
+                s
+            ))),
+        }
+    }
+
+    fn parse_kind<'a>(&mut self, s: &'a str) -> &'a str {
+        if Self::parse_major_seventh(s).is_some() {
+            return s;
+        }
+
+        if let Some(s) = s.strip_prefix("ø") {
+            self.kind = Kind::Minor;
+            self.seventh = Some(Seventh::Minor);
+            self.alterations = vec![Alteration::FlatFifth];
+            return s;
+        }
+
+        if let Some(s) = s.strip_prefix('5') {
+            if s.is_empty() || s.starts_with('/') {
+                self.kind = Kind::Power;
+                return s;
+            }
+        }
 
-        if l2 > 0 {
-            if let Ok(chord) = SimpleChord::try_from(&s[..l1 + l2]) {
-                return Ok((chord, &s[l1 + l2..]));
+        if !s.starts_with("omit") {
+            if let Some(s) = s.strip_prefix('o') {
+                self.kind = Kind::Diminished;
+                return s;
             }
         }
-        if l1 > 0 {
-            if let Ok(chord) = SimpleChord::try_from(&s[..l1]) {
-                return Ok((chord, &s[l1..]));
+
+        for (prefix, kind) in KIND_PREFIXES {
+            if let Some(s) = s.strip_prefix(prefix) {
+                self.kind = kind.clone();
+                return s;
             }
         }
-        Err(Error::Parse(
-            "can not parse a simple chord from an empty string".into(),
-        ))
+
+        s
     }
 
-    fn parse_kind<'a>(s: &'a str) -> (Kind, &'a str) {
-        if s.len() >= 4 {
-            let l4: usize = s.chars().take(4).map(|c| c.len_utf8()).sum();
-            match &s[..l4] {
-                "sus2" => return (Kind::Suspended2, &s[l4..]),
-                "sus4" => return (Kind::Suspended4, &s[l4..]),
-                _ => (),
+    fn parse_number(s: &str) -> Option<(Option<Extension>, &str)> {
+        if let Some(s) = s.strip_prefix("13") {
+            Some((Some(Extension::Thirteenth), s))
+        } else if let Some(s) = s.strip_prefix("11") {
+            Some((Some(Extension::Eleventh), s))
+        } else if let Some(s) = s.strip_prefix('9') {
+            Some((Some(Extension::Ninth), s))
+        } else {
+            s.strip_prefix('7').map(|s| (None, s))
+        }
+    }
+
+    /// Parses a spelled out major quality, the outer option tells whether a major seventh is
+    /// implied and the inner one holds its extension.
+    fn parse_major_seventh(s: &str) -> Option<(Option<Option<Extension>>, &str)> {
+        for prefix in MAJOR_SEVENTH_PREFIXES {
+            if let Some(s) = s.strip_prefix(prefix) {
+                match Self::parse_number(s) {
+                    Some((extension, s)) => return Some((Some(extension), s)),
+                    None if *prefix == "Δ" || *prefix == "^" => return Some((Some(None), s)),
+                    None if *prefix == "ma" => (),
+                    None => return Some((None, s)),
+                }
+            }
+        }
+        None
+    }
+
+    fn parse_modifier<'a>(&mut self, s: &'a str) -> Option<&'a str> {
+        for (prefix, alteration) in ALTERATION_PREFIXES {
+            if let Some(s) = s.strip_prefix(prefix) {
+                insert_sorted(&mut self.alterations, alteration.clone());
+                return Some(s);
+            }
+        }
+
+        for prefix in ["6/9", "69"] {
+            if let Some(s) = s.strip_prefix(prefix) {
+                insert_sorted(&mut self.added, Added::Sixth);
+                insert_sorted(&mut self.added, Added::Ninth);
+                return Some(s);
             }
         }
 
-        if s.len() >= 3 {
-            let l3: usize = s.chars().take(3).map(|c| c.len_utf8()).sum();
-            match &s[..l3] {
-                "dim" => return (Kind::Diminished, &s[l3..]),
-                "aug" => return (Kind::Augmented, &s[l3..]),
-                "sus" => return (Kind::Suspended4, &s[l3..]),
-                _ => (),
+        if let Some(s) = s.strip_prefix("add") {
+            for (prefix, added) in ADDED_PREFIXES {
+                if let Some(s) = s.strip_prefix(prefix) {
+                    insert_sorted(&mut self.added, added.clone());
+                    return Some(s);
+                }
             }
+            return None;
         }
 
-        if s.len() >= 1 {
-            let l1 = s.chars().next().map_or(0, |c| c.len_utf8());
-            match &s[..l1] {
-                "m" => return (Kind::Minor, &s[l1..]),
-                "°" => return (Kind::Diminished, &s[l1..]),
-                "+" => return (Kind::Augmented, &s[l1..]),
-                _ => (),
+        if let Some(s) = s.strip_prefix("sus") {
+            if let Some(s) = s.strip_prefix('2') {
+                self.kind = Kind::Suspended2;
+                return Some(s);
             }
+            self.kind = Kind::Suspended4;
+            return Some(s.strip_prefix('4').unwrap_or(s));
         }
 
-        (Kind::Major, s)
+        for (prefix, omission) in OMISSION_PREFIXES {
+            if let Some(s) = s.strip_prefix(prefix) {
+                insert_sorted(&mut self.omissions, omission.clone());
+                return Some(s);
+            }
+        }
+
+        if let Some((seventh, s)) = Self::parse_major_seventh(s) {
+            if let Some(extension) = seventh {
+                self.seventh = Some(Seventh::Major);
+                self.extension = extension;
+            }
+            return Some(s);
+        }
+
+        if let Some((extension, s)) = Self::parse_number(s) {
+            if self.seventh.is_none() {
+                self.seventh = Some(if self.kind == Kind::Diminished && extension.is_none() {
+                    Seventh::Diminished
+                } else {
+                    Seventh::Minor
+                });
+            }
+            if extension > self.extension {
+                self.extension = extension;
+            }
+            return Some(s);
+        }
+
+        if let Some(s) = s.strip_prefix('6') {
+            insert_sorted(&mut self.added, Added::Sixth);
+            return Some(s);
+        }
+
+        if let Some(s) = s.strip_prefix('2') {
+            insert_sorted(&mut self.added, Added::Second);
+            return Some(s);
+        }
+
+        None
     }
 
-    fn parse_base<'a>(s: &'a str) -> Result<(Option<SimpleChord>, &'a str), Error> {
-        if s.len() == 0 {
+    fn parse_modifiers<'a>(&mut self, mut s: &'a str) -> &'a str {
+        loop {
+            let rest = s.trim_start_matches(['(', ')', ',']);
+            if rest.is_empty() || rest.starts_with('/') && !rest.starts_with("/9") {
+                return rest;
+            }
+            match self.parse_modifier(rest) {
+                Some(rest) => s = rest,
+                None => return s,
+            }
+        }
+    }
+
+    fn parse_base(s: &str, parse_root: RootParser) -> Result<(Option<SimpleChord>, &str), Error> {
+        if s.is_empty() {
             return Ok((None, s));
         }
-        Self::parse_simple_chord(s).map(|(chord, s)| (Some(chord), s))
+        parse_root(s).map(|(chord, s)| (Some(chord), s))
     }
 
-    fn parse_var<'a>(s: &'a str) -> (&'a str, &'a str) {
+    fn parse_var(s: &str) -> (&str, &str) {
         match s.split_once('/') {
             Some((var, s)) => (var, s),
             None => (s, ""),
         }
     }
+
+    fn parse(s: &str, parse_root: RootParser) -> Result<Self, Error> {
+        let (main, s) = parse_root(s)?;
+        let mut chord = Self {
+            main,
+            ..Default::default()
+        };
+        let s = chord.parse_kind(s);
+        let s = chord.parse_modifiers(s);
+        let (var, s) = Self::parse_var(s);
+        let (base, _) = Self::parse_base(s, parse_root)?;
+        chord.var = var.to_string();
+        chord.base = base;
+        Ok(chord)
+    }
+}
+
+/// The Roman numeral of the level relative to the scale of the mode, chromatic levels are
+/// written with an accidental relative to the scale degree of the major scale. The tritone is
+/// `#IV` as in `Spelling`.
+fn roman_numeral(level: u8, mode: Mode) -> String {
+    let scale = mode.scale();
+    let major = Mode::Major.scale();
+    if let Some(degree) = scale.iter().position(|other| *other == level) {
+        ROMAN_NUMERALS[degree].into()
+    } else if mode == Mode::Minor && level == 11 {
+        // the raised leading tone is part of harmonic minor
+        ROMAN_NUMERALS[6].into()
+    } else if let Some(degree) = major.iter().position(|other| *other == level) {
+        let accidental = if scale[degree] < level { "#" } else { "b" };
+        format!("{}{}", accidental, ROMAN_NUMERALS[degree])
+    } else if level == 6 {
+        format!("#{}", ROMAN_NUMERALS[3])
+    } else {
+        let degree = major
+            .iter()
+            .position(|other| *other == level + 1)
+            .unwrap_or_default();
+        format!("b{}", ROMAN_NUMERALS[degree])
+    }
+}
+
+/// The Roman numeral of the diatonic triad on the level, if the level is part of the scale.
+fn diatonic_roman_numeral(level: u8, mode: Mode) -> Option<String> {
+    let degree = mode.scale().iter().position(|other| *other == level)?;
+    Some(match mode.diatonic_kind(degree) {
+        Kind::Minor => ROMAN_NUMERALS[degree].to_lowercase(),
+        Kind::Diminished => format!("{}°", ROMAN_NUMERALS[degree].to_lowercase()),
+        Kind::Augmented => format!("{}+", ROMAN_NUMERALS[degree]),
+        _ => ROMAN_NUMERALS[degree].into(),
+    })
+}
+
+/// The figured bass symbol if the bass is the third, fifth or seventh of a triad or seventh chord.
+fn figure_for_bass(chord: &Chord, base: &SimpleChord) -> Option<&'static str> {
+    if chord.extension.is_some() {
+        return None;
+    }
+    let semitones = (base.level() + 12 - chord.main.level()) % 12;
+    let degree = chord
+        .intervals()
+        .into_iter()
+        .find(|interval| interval.semitones() % 12 == semitones)?
+        .degree();
+    match (degree, chord.seventh.is_some()) {
+        (3, false) => Some("6"),
+        (5, false) => Some("64"),
+        (3, true) => Some("65"),
+        (5, true) => Some("43"),
+        (7, true) => Some("42"),
+        _ => None,
+    }
+}
+
+fn insert_sorted<T: Ord>(vec: &mut Vec<T>, value: T) {
+    if let Err(index) = vec.binary_search(&value) {
+        vec.insert(index, value);
+    }
 }
 
 impl FromStr for Chord {
     type Err = Error;
 
-    fn from_str(s_in: &str) -> Result<Self, Self::Err> {
-        let s = s_in;
-        let (main, s) = Self::parse_simple_chord(s)?;
-        let (kind, s) = Self::parse_kind(s);
-        let (var, s) = Self::parse_var(s);
-        let (base, _) = Self::parse_base(s)?;
-
-        Ok(Self {
-            main,
-            base,
-            kind,
-            var: var.to_string(),
-        })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Self::parse_simple_chord)
     }
 }
 
@@ -230,7 +794,7 @@ mod test {
             Ok(Chord::new(0).sus4()),
             Ok(Chord::new(0).sus2()),
             Ok(Chord::new(0)),
-            Ok(Chord::new(3).var("add9".into())),
+            Ok(Chord::new(3).added(Added::Ninth)),
             Ok(Chord::new(3)
                 .minor()
                 .var("47".into())
@@ -241,4 +805,179 @@ mod test {
             assert_eq!(&Chord::from_str(input), output);
         }
     }
+
+    #[test]
+    fn chord_from_str_extended() {
+        let inputs = vec![
+            "Cmaj7", "CM7", "CΔ7", "CΔ", "Cm7b5", "Cø", "C7#9", "Cadd2", "C6/9", "Cno3", "Cdim7",
+            "C7sus4", "CmM7", "C13", "Cmaj9#11", "C7(b9)/E", "C5", "C-7", "Cmadd9",
+        ];
+        let outputs = vec![
+            Chord::new(3).seventh(Seventh::Major),
+            Chord::new(3).seventh(Seventh::Major),
+            Chord::new(3).seventh(Seventh::Major),
+            Chord::new(3).seventh(Seventh::Major),
+            Chord::new(3)
+                .minor()
+                .seventh(Seventh::Minor)
+                .alter(Alteration::FlatFifth),
+            Chord::new(3)
+                .minor()
+                .seventh(Seventh::Minor)
+                .alter(Alteration::FlatFifth),
+            Chord::new(3)
+                .seventh(Seventh::Minor)
+                .alter(Alteration::SharpNinth),
+            Chord::new(3).added(Added::Second),
+            Chord::new(3).added(Added::Sixth).added(Added::Ninth),
+            Chord::new(3).omit(Omission::Third),
+            Chord::new(3).dim().seventh(Seventh::Diminished),
+            Chord::new(3).sus4().seventh(Seventh::Minor),
+            Chord::new(3).minor().seventh(Seventh::Major),
+            Chord::new(3).extension(Extension::Thirteenth),
+            Chord::new(3)
+                .seventh(Seventh::Major)
+                .extension(Extension::Ninth)
+                .alter(Alteration::SharpEleventh),
+            Chord::new(3)
+                .seventh(Seventh::Minor)
+                .alter(Alteration::FlatNinth)
+                .base(SimpleChord::new(7)),
+            Chord::new(3).power(),
+            Chord::new(3).minor().seventh(Seventh::Minor),
+            Chord::new(3).minor().added(Added::Ninth),
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(&Chord::from_str(input).unwrap(), output, "{}", input);
+        }
+    }
+
+    #[test]
+    fn chord_format() {
+        let inputs = vec![
+            "A", "Bbm", "C#dim", "Dsus2", "Cmaj7", "CΔ", "Cø7", "Cm7-5", "C7#9", "C2", "C6/9/E",
+            "Cm6", "Cno3", "C°7", "C9sus4", "CmM7", "Cmaj13", "Cadd9", "C7alt", "Cm47/F",
+        ];
+        let outputs = vec![
+            "A", "Bbm", "C#dim", "Dsus2", "Cmaj7", "Cmaj7", "Cm7b5", "Cm7b5", "C7#9", "Cadd2",
+            "C6/9/E", "Cm6", "Cno3", "Cdim7", "C9sus4", "Cmmaj7", "Cmaj13", "Cadd9", "C7alt",
+            "Cm47/F",
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let chord = Chord::from_str(input).unwrap();
+            assert_eq!(&chord.format(&Key::default()), output);
+            assert_eq!(Chord::from_str(output).unwrap(), chord);
+        }
+    }
+
+    #[test]
+    fn chord_tones() {
+        let inputs = [
+            "A", "C#maj7", "Am7b5", "D#dim7", "G7#9", "C6/9", "E13", "Bsus4",
+        ];
+        let outputs = [
+            vec![("R", "A"), ("3", "C#"), ("5", "E")],
+            vec![("R", "C#"), ("3", "E#"), ("5", "G#"), ("7", "B#")],
+            vec![("R", "A"), ("m3", "C"), ("b5", "Eb"), ("b7", "G")],
+            vec![("R", "D#"), ("m3", "F#"), ("b5", "A"), ("bb7", "C")],
+            vec![
+                ("R", "G"),
+                ("3", "B"),
+                ("5", "D"),
+                ("b7", "F"),
+                ("#9", "A#"),
+            ],
+            vec![("R", "C"), ("3", "E"), ("5", "G"), ("6", "A"), ("9", "D")],
+            vec![
+                ("R", "E"),
+                ("3", "G#"),
+                ("5", "B"),
+                ("b7", "D"),
+                ("9", "F#"),
+                ("13", "C#"),
+            ],
+            vec![("R", "B"), ("4", "E"), ("5", "F#")],
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let tones = Chord::from_str(input)
+                .unwrap()
+                .tones(&Key::default())
+                .into_iter()
+                .map(|tone| (tone.interval.format(), tone.note.format()))
+                .collect::<Vec<(String, String)>>();
+            let output = output
+                .iter()
+                .map(|(interval, note)| (interval.to_string(), note.to_string()))
+                .collect::<Vec<(String, String)>>();
+            assert_eq!(tones, output, "{}", input);
+        }
+
+        assert_eq!(
+            Chord::from_str("C/E")
+                .unwrap()
+                .pitch_classes(&SimpleChord::new(5).into()),
+            vec![
+                SimpleChord::new(0),
+                SimpleChord::new(3),
+                SimpleChord::new(8)
+            ],
+        );
+    }
+
+    #[test]
+    fn chord_nashville() {
+        let inputs = [
+            "1", "4/3", "6m", "b7", "5sus4", "2m7", "#4m7b5", "b3maj7/5", "16/9",
+        ];
+        let outputs = [
+            Chord::new(0),
+            Chord::new(5).base(SimpleChord::new(4)),
+            Chord::new(9).minor(),
+            Chord::new(10),
+            Chord::new(7).sus4(),
+            Chord::new(2).minor().seventh(Seventh::Minor),
+            Chord::new(6)
+                .minor()
+                .seventh(Seventh::Minor)
+                .alter(Alteration::FlatFifth),
+            Chord::new(3)
+                .seventh(Seventh::Major)
+                .base(SimpleChord::new(7)),
+            Chord::new(0).added(Added::Sixth).added(Added::Ninth),
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let chord = Chord::from_nashville(input).unwrap();
+            assert_eq!(&chord, output, "{}", input);
+            assert_eq!(Chord::from_nashville(&chord.format_nashville()), Ok(chord));
+        }
+        assert!(Chord::from_nashville("C").is_err());
+        assert!(Chord::from_nashville("8").is_err());
+    }
+
+    #[test]
+    fn chord_roman() {
+        let inputs = [
+            "1", "2m", "3m7", "4maj7", "57", "6m", "7dim", "7m7b5", "27", "37", "b7", "b3+", "1/3",
+            "5/2", "57/4", "4/5", "5sus4", "7dim7", "#4m7b5", "b2",
+        ];
+        let outputs = [
+            "I", "ii", "iii7", "IVM7", "V7", "vi", "vii°", "viiø7", "V7/V", "V7/vi", "bVII",
+            "bIII+", "I6", "V64", "V42", "IV/5", "Vsus4", "vii°7", "#ivø7", "bII",
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(
+                &Chord::from_nashville(input)
+                    .unwrap()
+                    .format_roman(Mode::Major),
+                output,
+                "{}",
+                input
+            );
+        }
+    }
 }
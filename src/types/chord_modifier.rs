@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use super::Interval;
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub enum Seventh {
+    Minor,
+    Major,
+    Diminished,
+}
+
+impl Seventh {
+    pub fn interval(&self) -> Interval {
+        match self {
+            Seventh::Minor => Interval::new(7, -1),
+            Seventh::Major => Interval::new(7, 0),
+            Seventh::Diminished => Interval::new(7, -2),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone)]
+pub enum Extension {
+    Ninth,
+    Eleventh,
+    Thirteenth,
+}
+
+impl Extension {
+    pub fn format(&self) -> &str {
+        match self {
+            Extension::Ninth => "9",
+            Extension::Eleventh => "11",
+            Extension::Thirteenth => "13",
+        }
+    }
+
+    /// The extensions stacked on top of the seventh, the eleventh is left out of thirteenth
+    /// chords unless the chord is minor, as it clashes with a major third.
+    pub fn intervals(&self, minor: bool) -> Vec<Interval> {
+        match self {
+            Extension::Ninth => vec![Interval::new(9, 0)],
+            Extension::Eleventh => vec![Interval::new(9, 0), Interval::new(11, 0)],
+            Extension::Thirteenth if minor => vec![
+                Interval::new(9, 0),
+                Interval::new(11, 0),
+                Interval::new(13, 0),
+            ],
+            Extension::Thirteenth => vec![Interval::new(9, 0), Interval::new(13, 0)],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone)]
+pub enum Added {
+    Second,
+    Fourth,
+    Sixth,
+    Ninth,
+    Eleventh,
+    Thirteenth,
+}
+
+impl Added {
+    pub fn format(&self) -> &str {
+        match self {
+            Added::Second => "2",
+            Added::Fourth => "4",
+            Added::Sixth => "6",
+            Added::Ninth => "9",
+            Added::Eleventh => "11",
+            Added::Thirteenth => "13",
+        }
+    }
+
+    pub fn interval(&self) -> Interval {
+        match self {
+            Added::Second => Interval::new(2, 0),
+            Added::Fourth => Interval::new(4, 0),
+            Added::Sixth => Interval::new(6, 0),
+            Added::Ninth => Interval::new(9, 0),
+            Added::Eleventh => Interval::new(11, 0),
+            Added::Thirteenth => Interval::new(13, 0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone)]
+pub enum Alteration {
+    FlatFifth,
+    SharpFifth,
+    FlatNinth,
+    SharpNinth,
+    SharpEleventh,
+    FlatThirteenth,
+}
+
+impl Alteration {
+    pub fn format(&self) -> &str {
+        match self {
+            Alteration::FlatFifth => "b5",
+            Alteration::SharpFifth => "#5",
+            Alteration::FlatNinth => "b9",
+            Alteration::SharpNinth => "#9",
+            Alteration::SharpEleventh => "#11",
+            Alteration::FlatThirteenth => "b13",
+        }
+    }
+
+    pub fn interval(&self) -> Interval {
+        match self {
+            Alteration::FlatFifth => Interval::new(5, -1),
+            Alteration::SharpFifth => Interval::new(5, 1),
+            Alteration::FlatNinth => Interval::new(9, -1),
+            Alteration::SharpNinth => Interval::new(9, 1),
+            Alteration::SharpEleventh => Interval::new(11, 1),
+            Alteration::FlatThirteenth => Interval::new(13, -1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone)]
+pub enum Omission {
+    Third,
+    Fifth,
+}
+
+impl Omission {
+    pub fn format(&self) -> &str {
+        match self {
+            Omission::Third => "no3",
+            Omission::Fifth => "no5",
+        }
+    }
+
+    pub fn degree(&self) -> u8 {
+        match self {
+            Omission::Third => 3,
+            Omission::Fifth => 5,
+        }
+    }
+}
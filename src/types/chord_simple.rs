@@ -1,15 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use super::{Chord, ChordTone, Key, Note, Spelling};
 use crate::error::Error;
 
-static CHORD_STRINGS_SHARP: &[&str] = &[
-    "A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#",
-];
-static CHORD_STRINGS_FLAT: &[&str] = &[
-    "A", "Bb", "B", "C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab",
+static NASHVILLE_NUMBERS: &[&str] = &[
+    "1", "b2", "2", "b3", "3", "4", "b5", "5", "b6", "6", "b7", "7",
 ];
 
-#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Clone)]
 pub struct SimpleChord {
     #[serde(deserialize_with = "float_or_int_to_int")]
     level: u8,
@@ -36,15 +34,9 @@ impl TryFrom<&str> for SimpleChord {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if let Some(level) = CHORD_STRINGS_SHARP.iter().position(|e| &s == e) {
-            Ok(Self::new(level as u8))
-        } else {
-            if let Some(level) = CHORD_STRINGS_FLAT.iter().position(|e| &s == e) {
-                Ok(Self::new(level as u8))
-            } else {
-                Err(Error::Parse(format!("unknown level, {}", s)))
-            }
-        }
+        Note::try_from(s)
+            .map(|note| Self::new(note.level()))
+            .map_err(|_| Error::Parse(format!("unknown level, {}", s)))
     }
 }
 
@@ -53,6 +45,10 @@ impl SimpleChord {
         Self { level: level % 12 }
     }
 
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
     pub fn transpose(&self, level: u8) -> Self {
         Self::new(self.level + level)
     }
@@ -61,13 +57,26 @@ impl SimpleChord {
         self.transpose(12 - key.level)
     }
 
-    pub fn format(&self, key: &Self) -> &'static str {
-        match key.level {
-            0 | 2 | 3 | 5 | 7 | 9 | 10 => {
-                CHORD_STRINGS_SHARP[((self.level + key.level) % 12) as usize]
-            }
-            _ => CHORD_STRINGS_FLAT[((self.level + key.level) % 12) as usize],
-        }
+    pub fn format(&self, key: &Key) -> String {
+        self.format_spelled(key, Spelling::default())
+    }
+
+    pub fn format_spelled(&self, key: &Key, spelling: Spelling) -> String {
+        spelling.spell(self, key).format()
+    }
+
+    /// Formats the level as Nashville number, this expects a normalized level.
+    pub fn format_nashville(&self) -> &'static str {
+        NASHVILLE_NUMBERS[self.level as usize]
+    }
+
+    /// The tones of the major triad built on this root.
+    pub fn tones(&self, key: &Key) -> Vec<ChordTone> {
+        Chord::new(self.level).tones(key)
+    }
+
+    pub fn pitch_classes(&self, key: &Key) -> Vec<Self> {
+        Chord::new(self.level).pitch_classes(key)
     }
 }
 
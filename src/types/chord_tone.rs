@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Interval, Note, SimpleChord};
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct ChordTone {
+    pub interval: Interval,
+    pub pitch: SimpleChord,
+    pub note: Note,
+}
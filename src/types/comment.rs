@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum CommentStyle {
+    #[default]
+    Normal,
+    Italic,
+    Boxed,
+    /// A comment in the source file that is not meant to be printed.
+    Hidden,
+}
+
+/// A note for the performers, e.g. "half-time feel" or "only piano".
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Comment {
+    pub text: String,
+    pub style: CommentStyle,
+}
+
+impl Comment {
+    pub fn new(text: String, style: CommentStyle) -> Self {
+        Self { text, style }
+    }
+}
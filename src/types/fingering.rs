@@ -0,0 +1,486 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::{Chord, Instrument, Interval, Key, Tuning};
+use crate::error::Error;
+
+/// The number of frets a hand can cover without moving.
+const SPAN: u8 = 4;
+
+/// The highest fret a generated fingering starts on.
+const MAX_POSITION: u8 = 12;
+
+/// The number of fingerings returned for a chord.
+const MAX_FINGERINGS: usize = 5;
+
+/// The most strings fingerings are searched for, the search grows exponentially with them.
+const MAX_STRINGS: usize = 8;
+
+/// The index finger laid across several strings on the same fret.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Barre {
+    pub fret: u8,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// A way to play a chord on a fretted instrument, strings are ordered from low to high.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Fingering {
+    frets: Vec<Option<u8>>,
+    fingers: Vec<Option<u8>>,
+    barre: Option<Barre>,
+}
+
+impl Fingering {
+    /// Takes the absolute fret per string (`None` for muted strings) and assigns the fingers.
+    pub fn new(frets: Vec<Option<u8>>) -> Self {
+        let (fingers, barre) = assign_fingers(&frets).unwrap_or((vec![None; frets.len()], None));
+        Self {
+            frets,
+            fingers,
+            barre,
+        }
+    }
+
+    /// Overrides the assigned fingers (1 = index to 4 = pinky), a finger placed on several
+    /// strings of the same fret becomes a barre.
+    pub fn fingered(self, fingers: Vec<Option<u8>>) -> Self {
+        let mut result = self;
+        result.barre = (1..=4).find_map(|finger| {
+            let strings = fingers
+                .iter()
+                .enumerate()
+                .filter(|(_, other)| **other == Some(finger))
+                .map(|(string, _)| string)
+                .collect::<Vec<usize>>();
+            let fret = result.frets.get(*strings.first()?).copied().flatten()?;
+            if strings.len() > 1
+                && strings
+                    .iter()
+                    .all(|string| result.frets[*string] == Some(fret))
+            {
+                Some(Barre {
+                    fret,
+                    from: strings[0],
+                    to: strings[strings.len() - 1],
+                })
+            } else {
+                None
+            }
+        });
+        result.fingers = fingers;
+        result
+    }
+
+    pub fn frets(&self) -> &[Option<u8>] {
+        &self.frets
+    }
+
+    pub fn fingers(&self) -> &[Option<u8>] {
+        &self.fingers
+    }
+
+    pub fn barre(&self) -> Option<&Barre> {
+        self.barre.as_ref()
+    }
+
+    /// The lowest fretted fret, where a diagram starts.
+    pub fn base_fret(&self) -> u8 {
+        self.frets
+            .iter()
+            .flatten()
+            .filter(|fret| **fret > 0)
+            .min()
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Whether every fretted string has a finger.
+    pub fn is_playable(&self) -> bool {
+        self.frets
+            .iter()
+            .zip(self.fingers.iter())
+            .all(|(fret, finger)| !matches!(fret, Some(fret) if *fret > 0) || finger.is_some())
+    }
+
+    /// Fingerings for the chord on the instrument, known shapes come first.
+    pub fn generate(chord: &Chord, instrument: &Instrument) -> Vec<Self> {
+        let mut result = instrument
+            .shapes()
+            .iter()
+            .filter(|(name, _)| Chord::from_str(name).ok().as_ref() == Some(chord))
+            .filter_map(|(_, frets)| Self::from_str(frets).ok())
+            .collect::<Vec<Self>>();
+        for fingering in search(chord, &instrument.tuning()) {
+            if result.len() >= MAX_FINGERINGS {
+                break;
+            }
+            if !result.contains(&fingering) {
+                result.push(fingering);
+            }
+        }
+        result
+    }
+
+    /// Writes the frets like `x32010`, separated by dashes if a fret has two digits.
+    pub fn format(&self) -> String {
+        let frets = self
+            .frets
+            .iter()
+            .map(|fret| fret.map_or("x".into(), |fret| fret.to_string()))
+            .collect::<Vec<String>>();
+        if frets.iter().all(|fret| fret.len() == 1) {
+            frets.concat()
+        } else {
+            frets.join("-")
+        }
+    }
+
+    /// Writes the fingering as the arguments of a ChordPro `{define}` after the chord name.
+    pub fn format_define(&self) -> String {
+        let base_fret = self.base_fret();
+        let frets = self
+            .frets
+            .iter()
+            .map(|fret| match fret {
+                None => "x".into(),
+                Some(0) => "0".into(),
+                Some(fret) => (fret + 1 - base_fret).to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        let fingers = self
+            .fingers
+            .iter()
+            .map(|finger| finger.unwrap_or_default().to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!(
+            "base-fret {} frets {} fingers {}",
+            base_fret, frets, fingers
+        )
+    }
+
+    /// Parses the arguments of a ChordPro `{define}` after the chord name.
+    pub fn from_define(s: &str) -> Result<Self, Error> {
+        let mut base_fret = 1;
+        let mut frets = Vec::default();
+        let mut fingers = Vec::default();
+        let mut target = None;
+        for token in s.split_whitespace() {
+            match token {
+                "base-fret" | "frets" | "fingers" => target = Some(token),
+                _ => match target {
+                    Some("base-fret") => {
+                        base_fret = token
+                            .parse::<u8>()
+                            .ok()
+                            .filter(|base_fret| *base_fret > 0)
+                            .ok_or(Error::Parse(format!("invalid base-fret, {}", token)))?
+                    }
+                    Some("frets") => frets.push(parse_fret(token)?),
+                    Some("fingers") => fingers.push(
+                        token
+                            .parse::<u8>()
+                            .ok()
+                            .filter(|finger| (1..=4).contains(finger)),
+                    ),
+                    _ => return Err(Error::Parse(format!("unknown define argument, {}", token))),
+                },
+            }
+        }
+        if frets.is_empty() {
+            return Err(Error::Parse(format!("no frets given, {}", s)));
+        }
+        let frets = frets
+            .into_iter()
+            .map(|fret| match fret {
+                Some(0) | None => Ok(fret),
+                Some(fret) => fret
+                    .checked_add(base_fret - 1)
+                    .map(Some)
+                    .ok_or(Error::Parse(format!("fret out of range, {}", s))),
+            })
+            .collect::<Result<Vec<Option<u8>>, Error>>()?;
+        let result = Self::new(frets);
+        if fingers.len() == result.frets.len() {
+            Ok(result.fingered(fingers))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+impl FromStr for Fingering {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let frets = if s.contains(['-', ' ']) {
+            s.split(['-', ' '])
+                .filter(|fret| !fret.is_empty())
+                .map(parse_fret)
+                .collect::<Result<Vec<Option<u8>>, Error>>()?
+        } else {
+            s.chars()
+                .map(|fret| parse_fret(&fret.to_string()))
+                .collect::<Result<Vec<Option<u8>>, Error>>()?
+        };
+        if frets.is_empty() {
+            return Err(Error::Parse(
+                "can not parse a fingering from an empty string".into(),
+            ));
+        }
+        Ok(Self::new(frets))
+    }
+}
+
+fn parse_fret(s: &str) -> Result<Option<u8>, Error> {
+    match s {
+        "x" | "X" | "N" | "-1" => Ok(None),
+        _ => s
+            .parse::<u8>()
+            .map(Some)
+            .map_err(|_| Error::Parse(format!("invalid fret, {}", s))),
+    }
+}
+
+/// Assigns fingers to the fretted strings, laying a barre across the lowest fret if more than
+/// four strings are fretted.
+fn assign_fingers(frets: &[Option<u8>]) -> Option<(Vec<Option<u8>>, Option<Barre>)> {
+    let mut fretted = frets
+        .iter()
+        .enumerate()
+        .filter_map(|(string, fret)| fret.filter(|fret| *fret > 0).map(|fret| (fret, string)))
+        .collect::<Vec<(u8, usize)>>();
+    fretted.sort();
+    let mut fingers = vec![None; frets.len()];
+    let (lowest, highest) = match (fretted.first(), fretted.last()) {
+        (Some(lowest), Some(highest)) => (lowest.0, highest.0),
+        _ => return Some((fingers, None)),
+    };
+    if highest - lowest >= SPAN {
+        return None;
+    }
+
+    if fretted.len() <= 4 {
+        for (finger, (_, string)) in fretted.iter().enumerate() {
+            fingers[*string] = Some(finger as u8 + 1);
+        }
+        return Some((fingers, None));
+    }
+
+    let on_lowest = fretted
+        .iter()
+        .filter(|(fret, _)| *fret == lowest)
+        .map(|(_, string)| *string)
+        .collect::<Vec<usize>>();
+    let from = *on_lowest.iter().min()?;
+    let to = *on_lowest.iter().max()?;
+    if frets[from..=to]
+        .iter()
+        .any(|fret| !matches!(fret, Some(fret) if *fret >= lowest))
+    {
+        return None;
+    }
+    let rest = fretted
+        .iter()
+        .filter(|(fret, _)| *fret > lowest)
+        .collect::<Vec<&(u8, usize)>>();
+    if rest.len() > 3 {
+        return None;
+    }
+    for string in on_lowest {
+        fingers[string] = Some(1);
+    }
+    for (finger, (_, string)) in rest.iter().enumerate() {
+        fingers[*string] = Some(finger as u8 + 2);
+    }
+    Some((
+        fingers,
+        Some(Barre {
+            fret: lowest,
+            from,
+            to,
+        }),
+    ))
+}
+
+/// Searches all hand positions for fingerings that contain the tones of the chord with the bass
+/// as the lowest note, ordered from the easiest to play. Reentrant tunings only keep the bass of
+/// slash chords and tunings with more than `MAX_STRINGS` strings are not searched.
+fn search(chord: &Chord, tuning: &Tuning) -> Vec<Fingering> {
+    let key = Key::default();
+    let tones = chord.tones(&key);
+    let bass = chord.bass().unwrap_or(chord.root()).level();
+    let check_bass = !tuning.is_reentrant() || chord.bass().is_some();
+    let allowed = tones
+        .iter()
+        .map(|tone| tone.pitch.level())
+        .chain(std::iter::once(bass))
+        .collect::<Vec<u8>>();
+    let required = tones
+        .iter()
+        .filter(|tone| tones.len() < 4 || tone.interval != Interval::new(5, 0))
+        .map(|tone| tone.pitch.level())
+        .chain(std::iter::once(bass))
+        .collect::<Vec<u8>>();
+    let strings = tuning.strings().len();
+    if strings > MAX_STRINGS {
+        return Vec::default();
+    }
+    let min_played = strings.saturating_sub(2).max(strings.min(3));
+
+    let mut result = Vec::default();
+    for position in 0..=MAX_POSITION {
+        let lowest = position.max(1);
+        let options = (0..strings)
+            .map(|string| {
+                std::iter::once(None)
+                    .chain((position == 0).then_some(Some(0)))
+                    .chain((lowest..lowest + SPAN).map(Some))
+                    .filter(|fret| {
+                        fret.is_none_or(|fret| allowed.contains(&tuning.level(string, fret)))
+                    })
+                    .collect::<Vec<Option<u8>>>()
+            })
+            .collect::<Vec<Vec<Option<u8>>>>();
+
+        let mut frets = Vec::with_capacity(strings);
+        combine(&options, &mut frets, &mut |frets| {
+            let played = frets
+                .iter()
+                .enumerate()
+                .filter_map(|(string, fret)| {
+                    fret.map(|fret| {
+                        (
+                            tuning.strings()[string] as u16 + fret as u16,
+                            tuning.level(string, fret),
+                        )
+                    })
+                })
+                .collect::<Vec<(u16, u8)>>();
+            let leading_muted = frets.iter().take_while(|fret| fret.is_none()).count();
+            if played.len() < min_played
+                || played.len() + leading_muted != frets.len()
+                || (check_bass && played.iter().min().map(|(_, level)| *level) != Some(bass))
+                || !required
+                    .iter()
+                    .all(|level| played.iter().any(|(_, other)| other == level))
+            {
+                return;
+            }
+            let fingering = Fingering::new(frets.to_vec());
+            if fingering.is_playable() && !result.contains(&fingering) {
+                result.push(fingering);
+            }
+        });
+    }
+    result.sort_by_key(|fingering| (difficulty(fingering), fingering.frets.clone()));
+    result
+}
+
+/// Calls the visitor with every combination of one option per string.
+fn combine(
+    options: &[Vec<Option<u8>>],
+    frets: &mut Vec<Option<u8>>,
+    visit: &mut impl FnMut(&[Option<u8>]),
+) {
+    match options.split_first() {
+        None => visit(frets),
+        Some((first, rest)) => {
+            // once a string is played the higher strings are played as well
+            let played = frets.last().is_some_and(Option::is_some);
+            for fret in first.iter().filter(|fret| !played || fret.is_some()) {
+                frets.push(*fret);
+                combine(rest, frets, visit);
+                frets.pop();
+            }
+        }
+    }
+}
+
+/// A rough measure of how hard a fingering is, muted strings, open strings between fretted ones
+/// and high positions count against it.
+fn difficulty(fingering: &Fingering) -> u32 {
+    let mut fingers = fingering.fingers.iter().flatten().collect::<Vec<&u8>>();
+    fingers.sort();
+    fingers.dedup();
+    let muted = fingering.frets.iter().filter(|fret| fret.is_none()).count();
+    let first = fingering.fingers.iter().position(Option::is_some);
+    let last = fingering.fingers.iter().rposition(Option::is_some);
+    let gaps = match (first, last) {
+        (Some(first), Some(last)) => fingering.frets[first..last]
+            .iter()
+            .filter(|fret| **fret == Some(0))
+            .count(),
+        _ => 0,
+    };
+    let position = if fingering.frets.iter().flatten().all(|fret| *fret <= SPAN) {
+        0
+    } else {
+        fingering.base_fret() as usize
+    };
+    (fingers.len() + 2 * muted + 2 * gaps + 2 * position + fingering.barre.is_some() as usize)
+        as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frets(chord: &str, instrument: Instrument) -> Vec<String> {
+        Fingering::generate(&Chord::from_str(chord).unwrap(), &instrument)
+            .iter()
+            .map(Fingering::format)
+            .collect()
+    }
+
+    #[test]
+    fn generate() {
+        assert_eq!(frets("C", Instrument::Guitar)[0], "x32010");
+        assert_eq!(frets("Am", Instrument::Guitar)[0], "x02210");
+        assert_eq!(frets("Bb", Instrument::Guitar)[0], "x13331");
+        assert!(frets("C#m", Instrument::Guitar).contains(&"x46654".to_string()));
+        assert!(frets("D/F#", Instrument::Guitar).contains(&"200232".to_string()));
+        assert_eq!(frets("C#m", Instrument::Guitar)[2], "9-11-11-9-9-9");
+        assert!(frets("G7", Instrument::Guitar).len() > 1);
+        assert!(frets("C", Instrument::Custom(Tuning::new(vec![40; 12]))).is_empty());
+
+        assert_eq!(frets("C", Instrument::Ukulele)[0], "0003");
+        assert_eq!(frets("Bb", Instrument::Ukulele)[0], "3211");
+        assert_eq!(frets("D", Instrument::DropD)[0], "000232");
+        assert_eq!(frets("G", Instrument::Mandolin)[0], "0023");
+        assert_eq!(frets("G", Instrument::Banjo)[0], "00000");
+
+        let fingering = Fingering::from_str("133211").unwrap();
+        assert_eq!(
+            fingering.fingers(),
+            [Some(1), Some(3), Some(4), Some(2), Some(1), Some(1)]
+        );
+        assert_eq!(
+            fingering.barre(),
+            Some(&Barre {
+                fret: 1,
+                from: 0,
+                to: 5
+            })
+        );
+    }
+
+    #[test]
+    fn define() {
+        let fingering =
+            Fingering::from_define("base-fret 3 frets x 1 3 3 3 1 fingers 0 1 2 3 4 1").unwrap();
+        assert_eq!(fingering.format(), "x35553");
+        assert_eq!(fingering.base_fret(), 3);
+        assert_eq!(fingering.barre().map(|barre| barre.fret), Some(3));
+        assert_eq!(
+            fingering.format_define(),
+            "base-fret 3 frets x 1 3 3 3 1 fingers 0 1 2 3 4 1"
+        );
+        assert!(Fingering::from_define("frets").is_err());
+        assert!(Fingering::from_define("base-fret 0 frets 1 1 1").is_err());
+        assert!(Fingering::from_define("base-fret 250 frets 9 9 9").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Key, Mode, Note, SimpleChord, Spelling};
+use crate::error::Error;
+
+/// Open-position guitar shapes preferred over generated fingerings.
+static GUITAR_SHAPES: &[(&str, &str)] = &[
+    ("C", "x32010"),
+    ("C7", "x32310"),
+    ("Cmaj7", "x32000"),
+    ("Cadd9", "x32030"),
+    ("D", "xx0232"),
+    ("Dm", "xx0231"),
+    ("D7", "xx0212"),
+    ("Dm7", "xx0211"),
+    ("Dsus2", "xx0230"),
+    ("Dsus4", "xx0233"),
+    ("E", "022100"),
+    ("Em", "022000"),
+    ("E7", "020100"),
+    ("Em7", "022030"),
+    ("Esus4", "022200"),
+    ("F", "133211"),
+    ("Fmaj7", "xx3210"),
+    ("G", "320003"),
+    ("G7", "320001"),
+    ("A", "x02220"),
+    ("Am", "x02210"),
+    ("A7", "x02020"),
+    ("Am7", "x02010"),
+    ("Asus2", "x02200"),
+    ("Asus4", "x02230"),
+    ("B7", "x21202"),
+];
+
+/// Common ukulele shapes preferred over generated fingerings.
+static UKULELE_SHAPES: &[(&str, &str)] = &[
+    ("C", "0003"),
+    ("C7", "0001"),
+    ("Dm", "2210"),
+    ("D", "2220"),
+    ("Em", "0432"),
+    ("E7", "1202"),
+    ("F", "2010"),
+    ("G", "0232"),
+    ("G7", "0212"),
+    ("Am", "2000"),
+    ("A", "2100"),
+    ("A7", "0100"),
+];
+
+/// The open strings of a fretted instrument as MIDI note numbers, ordered as in a chord diagram.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Tuning {
+    strings: Vec<u8>,
+}
+
+impl Tuning {
+    pub fn new(strings: Vec<u8>) -> Self {
+        Self { strings }
+    }
+
+    pub fn strings(&self) -> &[u8] {
+        &self.strings
+    }
+
+    /// The level of the given string fretted at the given fret.
+    pub fn level(&self, string: usize, fret: u8) -> u8 {
+        SimpleChord::new(((self.strings[string] as u16 + 3 + fret as u16) % 12) as u8).level()
+    }
+
+    /// Whether a string is tuned lower than the one before, e.g. the high g of a ukulele.
+    pub fn is_reentrant(&self) -> bool {
+        self.strings.windows(2).any(|pair| pair[1] < pair[0])
+    }
+
+    pub fn format(&self) -> String {
+        let key = Key::new(SimpleChord::new(3), Mode::Major);
+        self.strings
+            .iter()
+            .map(|pitch| {
+                let level = SimpleChord::new(pitch % 12 + 3).normalize(key.tonic());
+                format!(
+                    "{}{}",
+                    Spelling::default().spell(&level, &key).format(),
+                    (*pitch / 12) as i8 - 1
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl TryFrom<&str> for Tuning {
+    type Error = Error;
+
+    /// Parses notes with octaves up to `G9`, the highest MIDI note, e.g. `E2 A2 D3 G3 B3 E4`.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let strings = s
+            .split_whitespace()
+            .map(|string| {
+                let (note, octave) = Note::parse(string)?;
+                octave
+                    .parse::<u8>()
+                    .ok()
+                    .map(|octave| 12 * (octave as u16 + 1) + (note.level() as u16 + 9) % 12)
+                    .filter(|pitch| *pitch <= 127)
+                    .map(|pitch| pitch as u8)
+                    .ok_or(Error::Parse(format!("invalid octave, {}", string)))
+            })
+            .collect::<Result<Vec<u8>, Error>>()?;
+        if strings.is_empty() {
+            return Err(Error::Parse(
+                "can not parse a tuning from an empty string".into(),
+            ));
+        }
+        Ok(Self::new(strings))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub enum Instrument {
+    #[default]
+    Guitar,
+    DropD,
+    Ukulele,
+    Mandolin,
+    Bass,
+    Banjo,
+    Custom(Tuning),
+}
+
+impl Instrument {
+    pub fn tuning(&self) -> Tuning {
+        match self {
+            Instrument::Guitar => Tuning::new(vec![40, 45, 50, 55, 59, 64]),
+            Instrument::DropD => Tuning::new(vec![38, 45, 50, 55, 59, 64]),
+            Instrument::Ukulele => Tuning::new(vec![67, 60, 64, 69]),
+            Instrument::Mandolin => Tuning::new(vec![55, 62, 69, 76]),
+            Instrument::Bass => Tuning::new(vec![28, 33, 38, 43]),
+            Instrument::Banjo => Tuning::new(vec![67, 50, 55, 59, 62]),
+            Instrument::Custom(tuning) => tuning.clone(),
+        }
+    }
+
+    /// Known shapes as chord names and frets, preferred over generated fingerings.
+    pub fn shapes(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Instrument::Guitar => GUITAR_SHAPES,
+            Instrument::Ukulele => UKULELE_SHAPES,
+            _ => &[],
+        }
+    }
+
+    pub fn format(&self) -> String {
+        match self {
+            Instrument::Guitar => "guitar".into(),
+            Instrument::DropD => "drop-d".into(),
+            Instrument::Ukulele => "ukulele".into(),
+            Instrument::Mandolin => "mandolin".into(),
+            Instrument::Bass => "bass".into(),
+            Instrument::Banjo => "banjo".into(),
+            Instrument::Custom(tuning) => tuning.format(),
+        }
+    }
+}
+
+impl TryFrom<&str> for Instrument {
+    type Error = Error;
+
+    /// Parses the name of a preset or a tuning.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.trim().to_lowercase().as_str() {
+            "guitar" => Ok(Instrument::Guitar),
+            "drop-d" | "drop d" | "dropd" => Ok(Instrument::DropD),
+            "ukulele" | "uke" => Ok(Instrument::Ukulele),
+            "mandolin" => Ok(Instrument::Mandolin),
+            "bass" => Ok(Instrument::Bass),
+            "banjo" => Ok(Instrument::Banjo),
+            _ => Tuning::try_from(s)
+                .map(Instrument::Custom)
+                .map_err(|_| Error::Parse(format!("unknown instrument, {}", s))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn instrument_from_str() {
+        let inputs = [
+            "Guitar",
+            "drop-d",
+            "uke",
+            "E1 A1 D2 G2",
+            "D2 A2 D3 F#3 A3 D4",
+        ];
+        let outputs = [
+            "guitar",
+            "drop-d",
+            "ukulele",
+            "E1 A1 D2 G2",
+            "D2 A2 D3 F#3 A3 D4",
+        ];
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(&Instrument::try_from(*input).unwrap().format(), output);
+        }
+        assert_eq!(
+            Instrument::try_from("E1 A1 D2 G2").unwrap().tuning(),
+            Instrument::Bass.tuning()
+        );
+        assert!(Instrument::Ukulele.tuning().is_reentrant());
+        assert!(Instrument::try_from("kazoo").is_err());
+        assert!(Instrument::try_from("E30").is_err());
+        assert!(Instrument::try_from("A9").is_err());
+        assert_eq!(Tuning::new(vec![255]).level(0, 255), 9);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+static MAJOR_SCALE_SEMITONES: &[u8] = &[0, 2, 4, 5, 7, 9, 11];
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone)]
+pub struct Interval {
+    degree: u8,
+    alteration: i8,
+}
+
+impl Interval {
+    /// Creates an interval from its scale degree (1 = root, 9 = ninth, ...) and its alteration
+    /// in semitones relative to the major/perfect interval of that degree.
+    pub fn new(degree: u8, alteration: i8) -> Self {
+        Self {
+            degree: degree.max(1),
+            alteration,
+        }
+    }
+
+    pub fn degree(&self) -> u8 {
+        self.degree
+    }
+
+    pub fn alteration(&self) -> i8 {
+        self.alteration
+    }
+
+    /// The distance in semitones, compound intervals stay above the octave.
+    pub fn semitones(&self) -> u8 {
+        let index = (self.degree - 1) as usize;
+        let semitones = MAJOR_SCALE_SEMITONES[index % 7] as i8 + 12 * (index / 7) as i8;
+        (semitones + self.alteration).max(0) as u8
+    }
+
+    pub fn format(&self) -> String {
+        match (self.degree, self.alteration) {
+            (1, 0) => "R".into(),
+            (3, -1) => "m3".into(),
+            (degree, alteration) if alteration < 0 => {
+                format!("{}{}", "b".repeat(-alteration as usize), degree)
+            }
+            (degree, alteration) => format!("{}{}", "#".repeat(alteration as usize), degree),
+        }
+    }
+}
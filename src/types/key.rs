@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Kind, Note, SimpleChord, Spelling};
+use crate::error::Error;
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum Mode {
+    #[default]
+    Major,
+    Minor,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+}
+
+impl Mode {
+    /// The levels of the scale relative to the tonic.
+    pub fn scale(&self) -> [u8; 7] {
+        match self {
+            Mode::Major => [0, 2, 4, 5, 7, 9, 11],
+            Mode::Minor => [0, 2, 3, 5, 7, 8, 10],
+            Mode::Dorian => [0, 2, 3, 5, 7, 9, 10],
+            Mode::Phrygian => [0, 1, 3, 5, 7, 8, 10],
+            Mode::Lydian => [0, 2, 4, 6, 7, 9, 11],
+            Mode::Mixolydian => [0, 2, 4, 5, 7, 9, 10],
+            Mode::Locrian => [0, 1, 3, 5, 6, 8, 10],
+        }
+    }
+
+    /// The level of the tonic above the tonic of the major key with the same key signature.
+    pub fn offset(&self) -> u8 {
+        match self {
+            Mode::Major => 0,
+            Mode::Dorian => 2,
+            Mode::Phrygian => 4,
+            Mode::Lydian => 5,
+            Mode::Mixolydian => 7,
+            Mode::Minor => 9,
+            Mode::Locrian => 11,
+        }
+    }
+
+    /// The kind of the triad built on the given scale degree (0 = tonic) from scale tones only.
+    pub fn diatonic_kind(&self, degree: usize) -> Kind {
+        let scale = self.scale();
+        let root = scale[degree % 7];
+        let third = (scale[(degree + 2) % 7] + 12 - root) % 12;
+        let fifth = (scale[(degree + 4) % 7] + 12 - root) % 12;
+        match (third, fifth) {
+            (3, 6) => Kind::Diminished,
+            (3, _) => Kind::Minor,
+            (4, 8) => Kind::Augmented,
+            _ => Kind::Major,
+        }
+    }
+
+    pub fn format(&self) -> &str {
+        match self {
+            Mode::Major => "",
+            Mode::Minor => "m",
+            Mode::Dorian => " dorian",
+            Mode::Phrygian => " phrygian",
+            Mode::Lydian => " lydian",
+            Mode::Mixolydian => " mixolydian",
+            Mode::Locrian => " locrian",
+        }
+    }
+}
+
+impl TryFrom<&str> for Mode {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.trim() {
+            "" | "M" => return Ok(Mode::Major),
+            "m" | "-" => return Ok(Mode::Minor),
+            _ => (),
+        }
+        match s.trim().to_lowercase().as_str() {
+            "maj" | "major" | "ionian" | "dur" => Ok(Mode::Major),
+            "min" | "minor" | "aeolian" | "moll" => Ok(Mode::Minor),
+            "dorian" => Ok(Mode::Dorian),
+            "phrygian" => Ok(Mode::Phrygian),
+            "lydian" => Ok(Mode::Lydian),
+            "mixolydian" => Ok(Mode::Mixolydian),
+            "locrian" => Ok(Mode::Locrian),
+            _ => Err(Error::Parse(format!("unknown mode, {}", s))),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Key {
+    #[serde(flatten)]
+    tonic: SimpleChord,
+    #[serde(default)]
+    mode: Mode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<Note>,
+}
+
+impl Key {
+    pub fn new(tonic: SimpleChord, mode: Mode) -> Self {
+        Self {
+            tonic,
+            mode,
+            name: None,
+        }
+    }
+
+    /// Remembers how the tonic is written, e.g. `Gb` instead of `F#`.
+    pub fn spelled(self, name: Note) -> Self {
+        let mut result = self;
+        result.tonic = SimpleChord::new(name.level());
+        result.name = Some(name);
+        result
+    }
+
+    pub fn tonic(&self) -> &SimpleChord {
+        &self.tonic
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn name(&self) -> Option<&Note> {
+        self.name.as_ref()
+    }
+
+    /// The major key sharing the key signature, e.g. C for A minor or D dorian.
+    pub fn signature(&self) -> SimpleChord {
+        self.tonic.transpose(12 - self.mode.offset())
+    }
+
+    /// The key of the chord shapes played with a capo on the given fret.
+    pub fn shape(&self, capo: u8) -> Self {
+        Self::new(self.tonic.transpose(12 - capo % 12), self.mode)
+    }
+
+    pub fn format(&self) -> String {
+        self.format_spelled(Spelling::default())
+    }
+
+    pub fn format_spelled(&self, spelling: Spelling) -> String {
+        format!("{}{}", spelling.tonic(self).format(), self.mode.format())
+    }
+}
+
+impl From<SimpleChord> for Key {
+    fn from(tonic: SimpleChord) -> Self {
+        Self::new(tonic, Mode::Major)
+    }
+}
+
+impl TryFrom<&str> for Key {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (name, mode) = Note::parse(s.trim())?;
+        Ok(Self::new(SimpleChord::default(), Mode::try_from(mode)?).spelled(name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn key_from_str() {
+        let inputs = [
+            "C",
+            "Am",
+            "F#m",
+            "Bb",
+            "D dorian",
+            "G Mixolydian",
+            "Cmin",
+            "E minor",
+        ];
+        let outputs = [
+            Key::new(SimpleChord::new(3), Mode::Major),
+            Key::new(SimpleChord::new(0), Mode::Minor),
+            Key::new(SimpleChord::new(9), Mode::Minor),
+            Key::new(SimpleChord::new(1), Mode::Major),
+            Key::new(SimpleChord::new(5), Mode::Dorian),
+            Key::new(SimpleChord::new(10), Mode::Mixolydian),
+            Key::new(SimpleChord::new(3), Mode::Minor),
+            Key::new(SimpleChord::new(7), Mode::Minor),
+        ];
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let key = Key::try_from(*input).unwrap();
+            assert_eq!(key.tonic(), output.tonic(), "{}", input);
+            assert_eq!(key.mode(), output.mode(), "{}", input);
+        }
+        assert!(Key::try_from("C###").is_err());
+        assert!(Key::try_from(format!("C{}", "#".repeat(200)).as_str()).is_err());
+        assert_eq!(
+            outputs.iter().map(Key::format).collect::<Vec<String>>(),
+            [
+                "C",
+                "Am",
+                "F#m",
+                "Bb",
+                "D dorian",
+                "G mixolydian",
+                "Cm",
+                "Em"
+            ]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::{Chord, Key, Kind, Mode, SimpleChord};
+
+static DIATONIC_FIT_WEIGHT: f32 = 0.6;
+static FIRST_CHORD_WEIGHT: f32 = 0.1;
+static LAST_CHORD_WEIGHT: f32 = 0.15;
+static CADENCE_WEIGHT: f32 = 0.15;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeyCandidate {
+    pub key: Key,
+    /// A score between 0 and 1.
+    pub confidence: f32,
+}
+
+impl KeyCandidate {
+    /// Ranks all major and minor keys by how well they explain the absolute chords. The score
+    /// combines the share of diatonic chords, whether the first and last chord is the tonic and
+    /// the number of dominant to tonic cadences.
+    pub fn estimate(chords: &[Chord]) -> Vec<Self> {
+        if chords.is_empty() {
+            return Vec::default();
+        }
+
+        let mut candidates = (0..12)
+            .flat_map(|level| {
+                [Mode::Major, Mode::Minor]
+                    .into_iter()
+                    .map(move |mode| Key::new(SimpleChord::new(level), mode))
+            })
+            .map(|key| {
+                let degrees = chords
+                    .iter()
+                    .map(|chord| Self::degree(chord, &key))
+                    .collect::<Vec<Option<usize>>>();
+                let diatonic = degrees.iter().filter(|degree| degree.is_some()).count();
+                let cadences = degrees
+                    .windows(2)
+                    .filter(|window| window[0] == Some(4) && window[1] == Some(0))
+                    .count();
+
+                let confidence = DIATONIC_FIT_WEIGHT * diatonic as f32 / chords.len() as f32
+                    + FIRST_CHORD_WEIGHT * (degrees[0] == Some(0)) as u8 as f32
+                    + LAST_CHORD_WEIGHT * (degrees[degrees.len() - 1] == Some(0)) as u8 as f32
+                    + CADENCE_WEIGHT * (cadences as f32 / 2.).min(1.);
+                Self { key, confidence }
+            })
+            .collect::<Vec<Self>>();
+
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+
+    /// The scale degree (0 = tonic) of the chord if it is diatonic in the key, the major dominant
+    /// of harmonic minor counts as diatonic.
+    fn degree(chord: &Chord, key: &Key) -> Option<usize> {
+        let level = chord.root().normalize(key.tonic()).level();
+        let degree = key
+            .mode()
+            .scale()
+            .iter()
+            .position(|other| *other == level)?;
+        let diatonic = key.mode().diatonic_kind(degree);
+        match chord.kind() {
+            Kind::Suspended2 | Kind::Suspended4 | Kind::Power => Some(degree),
+            Kind::Major if key.mode() == Mode::Minor && degree == 4 => Some(degree),
+            kind if *kind == diatonic => Some(degree),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn estimate(chords: &str) -> Key {
+        let chords = chords
+            .split(' ')
+            .map(|chord| Chord::from_str(chord).unwrap())
+            .collect::<Vec<Chord>>();
+        KeyCandidate::estimate(&chords)[0].key.clone()
+    }
+
+    #[test]
+    fn estimate_key() {
+        let inputs = [
+            "G D Em C G",
+            "Am F C G Am",
+            "C F G7 C",
+            "Em C G D",
+            "Dm Bb C A7 Dm",
+            "E A B7 E",
+        ];
+        let outputs = ["G", "Am", "C", "Em", "Dm", "E"];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            let key = Key::try_from(*output).unwrap();
+            let estimate = estimate(input);
+            assert_eq!(estimate.tonic(), key.tonic(), "{}", input);
+            assert_eq!(estimate.mode(), key.mode(), "{}", input);
+        }
+        assert!(KeyCandidate::estimate(&[]).is_empty());
+    }
+}
@@ -1,15 +1,89 @@
 use serde::{Deserialize, Serialize};
 
-use super::{Part, SimpleChord};
+use super::{Comment, Key, Part, SimpleChord, Voicing};
+use crate::error::Error;
 
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Line {
     pub parts: Vec<Part>,
+    /// Makes the line a comment instead of lyrics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Comment>,
 }
 
 impl Line {
     pub fn new(parts: Vec<Part>) -> Self {
-        Self { parts }
+        Self {
+            parts,
+            comment: None,
+        }
+    }
+
+    /// Piano voicings of the chords in the line, each led from the one before.
+    pub fn voicings(&self, key: &Key) -> Vec<Voicing> {
+        let mut result: Vec<Voicing> = Vec::default();
+        for chord in self.parts.iter().filter_map(|part| part.chord.as_ref()) {
+            let voicing = Voicing::new(chord, key, result.last());
+            result.push(voicing);
+        }
+        result
+    }
+
+    /// Adds the lyrics of a translated line as another language, aligned on the chords. The
+    /// translation has the same chords or none at all, lyrics before its first chord go to the
+    /// first part.
+    pub fn add_translation(&mut self, translation: Line) -> Result<&mut Self, Error> {
+        let chord = |part: &Part| (part.chord.clone(), part.raw_chord.clone());
+        let has_chord = |part: &Part| part.chord.is_some() || part.raw_chord.is_some();
+        let mut leading = String::default();
+        let mut chords = Vec::default();
+        let mut texts: Vec<String> = Vec::default();
+        for part in translation.parts {
+            let text = part.languages.first().cloned().unwrap_or_default();
+            match texts.last_mut() {
+                _ if has_chord(&part) => {
+                    chords.push(chord(&part));
+                    texts.push(text);
+                }
+                Some(last) => last.push_str(&text),
+                None => leading.push_str(&text),
+            }
+        }
+        if !chords.is_empty()
+            && !chords.iter().cloned().eq(self
+                .parts
+                .iter()
+                .filter(|part| has_chord(part))
+                .map(chord))
+        {
+            return Err(Error::Parse(format!(
+                "translation chords differ, {}{}",
+                leading,
+                texts.concat()
+            )));
+        }
+
+        let mut texts = texts.into_iter();
+        for (index, part) in self.parts.iter_mut().enumerate() {
+            let mut text = match index {
+                0 => std::mem::take(&mut leading),
+                _ => String::default(),
+            };
+            if has_chord(part) {
+                text.push_str(&texts.next().unwrap_or_default());
+            }
+            part.languages.push(text);
+        }
+        Ok(self)
+    }
+
+    /// The number of languages of the lyrics.
+    pub fn languages(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| part.languages.len())
+            .max()
+            .unwrap_or_default()
     }
 
     pub fn normalize(&mut self, key: &SimpleChord) -> &mut Self {
@@ -19,3 +93,56 @@ impl Line {
         self
     }
 }
+
+impl From<Comment> for Line {
+    fn from(comment: Comment) -> Self {
+        Self {
+            parts: Vec::default(),
+            comment: Some(comment),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add_translation() {
+        let line = |parts: &[(&str, &str)]| {
+            Line::new(
+                parts
+                    .iter()
+                    .map(|part| Part::try_from(*part).unwrap())
+                    .collect(),
+            )
+        };
+        let mut original = line(&[("", "Du "), ("C", "bist "), ("G", "treu")]);
+        original
+            .add_translation(line(&[("C", "You are "), ("G", "faithful")]))
+            .unwrap()
+            .add_translation(line(&[("", "Tu "), ("C", "es "), ("G", "fidèle")]))
+            .unwrap()
+            .add_translation(line(&[("", "Treu bist du")]))
+            .unwrap();
+        assert_eq!(
+            original
+                .parts
+                .iter()
+                .map(|part| part.languages.clone())
+                .collect::<Vec<Vec<String>>>(),
+            [
+                ["Du ", "", "Tu ", "Treu bist du"],
+                ["bist ", "You are ", "es ", ""],
+                ["treu", "faithful", "fidèle", ""]
+            ]
+        );
+        assert!(original
+            .add_translation(line(&[("C", "You are "), ("F", "faithful")]))
+            .is_err());
+        assert!(original
+            .add_translation(line(&[("C", "You are faithful")]))
+            .is_err());
+        assert_eq!(original.languages(), 4);
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Additional information about a song, e.g. for planning services or licensing.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Metadata {
+    /// The tempo in beats per minute.
+    pub tempo: Option<u16>,
+    /// The time signature, e.g. `6/8`.
+    pub time: Option<String>,
+    /// The duration in seconds.
+    pub duration: Option<u32>,
+    pub ccli: Option<u32>,
+    pub copyright: Option<String>,
+    pub album: Option<String>,
+    pub year: Option<u16>,
+    pub composer: Option<String>,
+    pub lyricist: Option<String>,
+    /// Any other metadata as name and value.
+    #[serde(default)]
+    pub custom: Vec<(String, String)>,
+}
+
+impl Metadata {
+    /// The names of the fields as used by `set` and `entries`.
+    pub const KEYS: &'static [&'static str] = &[
+        "tempo",
+        "time",
+        "duration",
+        "ccli",
+        "copyright",
+        "album",
+        "year",
+        "composer",
+        "lyricist",
+    ];
+
+    /// Sets a field by its name, unknown names are kept as custom metadata.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<&mut Self, Error> {
+        let value = value.trim();
+        match name {
+            "tempo" => self.tempo = Some(parse_number(name, value)?),
+            "time" => self.time = Some(value.into()),
+            "duration" => self.duration = Some(parse_duration(value)?),
+            "ccli" => self.ccli = Some(parse_number(name, value)?),
+            "copyright" => self.copyright = Some(value.into()),
+            "album" => self.album = Some(value.into()),
+            "year" => self.year = Some(parse_number(name, value)?),
+            "composer" => self.composer = Some(value.into()),
+            "lyricist" => self.lyricist = Some(value.into()),
+            _ => self.custom.push((name.into(), value.into())),
+        }
+        Ok(self)
+    }
+
+    /// All metadata as names and values, the known fields first.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let fields = [
+            self.tempo.map(|tempo| tempo.to_string()),
+            self.time.clone(),
+            self.duration.map(format_duration),
+            self.ccli.map(|ccli| ccli.to_string()),
+            self.copyright.clone(),
+            self.album.clone(),
+            self.year.map(|year| year.to_string()),
+            self.composer.clone(),
+            self.lyricist.clone(),
+        ];
+        Self::KEYS
+            .iter()
+            .zip(fields)
+            .filter_map(|(name, value)| Some((name.to_string(), value?)))
+            .chain(self.custom.iter().cloned())
+            .collect()
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, Error> {
+    value
+        .parse::<T>()
+        .map_err(|_| Error::Parse(format!("invalid {}, {}", name, value)))
+}
+
+/// Parses seconds, minutes and seconds like `3:45` or hours, minutes and seconds.
+fn parse_duration(value: &str) -> Result<u32, Error> {
+    let invalid = || Error::Parse(format!("invalid duration, {}", value));
+    if value.matches(':').count() > 2 {
+        return Err(invalid());
+    }
+    value.split(':').try_fold(0u32, |duration, part| {
+        duration
+            .checked_mul(60)
+            .and_then(|duration| duration.checked_add(parse_number("duration", part).ok()?))
+            .ok_or_else(invalid)
+    })
+}
+
+fn format_duration(duration: u32) -> String {
+    format!("{}:{:02}", duration / 60, duration % 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata() {
+        let mut metadata = Metadata::default();
+        metadata
+            .set("tempo", "72")
+            .unwrap()
+            .set("duration", "225")
+            .unwrap()
+            .set("year", "1998")
+            .unwrap()
+            .set("arranger", "Someone")
+            .unwrap();
+        assert_eq!(metadata.tempo, Some(72));
+        assert_eq!(
+            metadata.entries(),
+            [
+                ("tempo".to_string(), "72".to_string()),
+                ("duration".to_string(), "3:45".to_string()),
+                ("year".to_string(), "1998".to_string()),
+                ("arranger".to_string(), "Someone".to_string()),
+            ]
+        );
+        assert_eq!(
+            metadata.set("duration", "3:45").unwrap().duration,
+            Some(225)
+        );
+        assert_eq!(
+            metadata.set("duration", "1:00:05").unwrap().duration,
+            Some(3605)
+        );
+        assert!(metadata.set("tempo", "fast").is_err());
+        assert!(metadata.set("duration", "4294967295:0").is_err());
+        assert!(metadata.set("duration", "1:2:3:4").is_err());
+        assert!(metadata.set("duration", "3:").is_err());
+    }
+}
@@ -1,13 +1,43 @@
+mod arrangement;
 mod chord;
+mod chord_modifier;
 mod chord_simple;
+mod chord_tone;
+mod comment;
+mod fingering;
+mod instrument;
+mod interval;
+mod key;
+mod key_candidate;
 mod line;
+mod metadata;
+mod note;
 mod part;
 mod section;
+mod section_code;
+mod section_kind;
 mod song;
+mod spelling;
+mod voicing;
 
+pub use arrangement::{Arrangement, ArrangementStep};
 pub use chord::{Chord, Kind};
+pub use chord_modifier::{Added, Alteration, Extension, Omission, Seventh};
 pub use chord_simple::SimpleChord;
+pub use chord_tone::ChordTone;
+pub use comment::{Comment, CommentStyle};
+pub use fingering::{Barre, Fingering};
+pub use instrument::{Instrument, Tuning};
+pub use interval::Interval;
+pub use key::{Key, Mode};
+pub use key_candidate::KeyCandidate;
 pub use line::Line;
+pub use metadata::Metadata;
+pub use note::Note;
 pub use part::Part;
 pub use section::Section;
+pub(crate) use section_code::{section_code, section_title, OPEN_LYRICS_CODES, OPEN_SONG_CODES};
+pub use section_kind::SectionKind;
 pub use song::Song;
+pub use spelling::Spelling;
+pub use voicing::{PianoNote, Voicing};
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use super::Interval;
+use crate::error::Error;
+
+static LETTERS: &[char] = &['A', 'B', 'C', 'D', 'E', 'F', 'G'];
+static LETTER_LEVELS: &[u8] = &[0, 2, 3, 5, 7, 8, 10];
+
+/// A spelled note name, e.g. `E#` and `F` share a level but are different notes.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Note {
+    letter: u8,
+    accidental: i8,
+}
+
+impl Note {
+    pub const fn new(letter: u8, accidental: i8) -> Self {
+        Self {
+            letter: letter % 7,
+            accidental,
+        }
+    }
+
+    /// Spells the level with the given letter, using as many accidentals as needed.
+    pub fn from_level(letter: u8, level: u8) -> Self {
+        let letter = letter % 7;
+        let difference = (level % 12 + 12 - LETTER_LEVELS[letter as usize]) % 12;
+        let accidental = if difference > 6 {
+            difference as i8 - 12
+        } else {
+            difference as i8
+        };
+        Self { letter, accidental }
+    }
+
+    pub fn letter(&self) -> u8 {
+        self.letter
+    }
+
+    pub fn accidental(&self) -> i8 {
+        self.accidental
+    }
+
+    pub fn level(&self) -> u8 {
+        ((LETTER_LEVELS[self.letter as usize] as i16 + self.accidental as i16).rem_euclid(12)) as u8
+    }
+
+    pub fn transpose(&self, interval: &Interval) -> Self {
+        Self::from_level(
+            self.letter + interval.degree() - 1,
+            self.level() + interval.semitones(),
+        )
+    }
+
+    pub fn format(&self) -> String {
+        let accidentals = if self.accidental < 0 {
+            "b".repeat(-self.accidental as usize)
+        } else {
+            "#".repeat(self.accidental as usize)
+        };
+        format!("{}{}", LETTERS[self.letter as usize], accidentals)
+    }
+
+    /// Parses a letter followed by up to two accidentals and returns the remaining string.
+    pub fn parse(s: &str) -> Result<(Self, &str), Error> {
+        let mut chars = s.chars();
+        let letter = chars
+            .next()
+            .and_then(|c| LETTERS.iter().position(|letter| *letter == c))
+            .ok_or(Error::Parse(format!("unknown note, {}", s)))?;
+        let mut accidental = 0;
+        let mut count = 0;
+        let mut rest = chars.as_str();
+        for c in chars {
+            match c {
+                '#' | '♯' => accidental += 1,
+                'b' | '♭' => accidental -= 1,
+                _ => break,
+            }
+            count += 1;
+            if count > 2 {
+                return Err(Error::Parse(format!("too many accidentals, {}", s)));
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        Ok((Self::new(letter as u8, accidental), rest))
+    }
+}
+
+impl TryFrom<&str> for Note {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match Self::parse(s)? {
+            (note, "") => Ok(note),
+            _ => Err(Error::Parse(format!("unknown note, {}", s))),
+        }
+    }
+}
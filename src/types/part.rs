@@ -7,6 +7,9 @@ use crate::error::Error;
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Part {
     pub chord: Option<Chord>,
+    /// A chord that could not be parsed, kept as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_chord: Option<String>,
     pub languages: Vec<String>,
 }
 
@@ -22,11 +25,12 @@ impl TryFrom<(&str, &str)> for Part {
 
     fn try_from(value: (&str, &str)) -> Result<Self, Self::Error> {
         Ok(Self {
-            chord: if value.0.len() == 0 {
+            chord: if value.0.is_empty() {
                 None
             } else {
                 Some(Chord::from_str(value.0)?)
             },
+            raw_chord: None,
             languages: vec![value.1.to_string()],
         })
     }
@@ -1,16 +1,35 @@
 use serde::{Deserialize, Serialize};
 
-use super::{Line, SimpleChord};
+use super::{Comment, Line, SectionKind, SimpleChord};
 
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Section {
+    /// The label as written, e.g. `Strophe 2`.
     pub title: String,
+    #[serde(default)]
+    pub kind: SectionKind,
+    #[serde(default)]
+    pub number: Option<u8>,
     pub lines: Vec<Line>,
 }
 
 impl Section {
+    /// Creates a section with the kind and number recognized from the title.
     pub fn new(title: String, lines: Vec<Line>) -> Self {
-        Self { title, lines }
+        let (kind, number) = SectionKind::parse(&title);
+        Self {
+            title,
+            kind,
+            number,
+            lines,
+        }
+    }
+
+    pub fn comments(&self) -> Vec<&Comment> {
+        self.lines
+            .iter()
+            .filter_map(|line| line.comment.as_ref())
+            .collect()
     }
 
     pub fn normalize(&mut self, key: &SimpleChord) -> &mut Self {
@@ -0,0 +1,54 @@
+use super::{Section, SectionKind};
+
+/// The OpenLyrics verse names of the section kinds.
+pub(crate) static OPEN_LYRICS_CODES: &[(&str, SectionKind)] = &[
+    ("i", SectionKind::Intro),
+    ("v", SectionKind::Verse),
+    ("p", SectionKind::PreChorus),
+    ("c", SectionKind::Chorus),
+    ("b", SectionKind::Bridge),
+    ("e", SectionKind::Outro),
+];
+
+/// The OpenSong section markers of the section kinds.
+pub(crate) static OPEN_SONG_CODES: &[(&str, SectionKind)] = &[
+    ("I", SectionKind::Intro),
+    ("V", SectionKind::Verse),
+    ("P", SectionKind::PreChorus),
+    ("C", SectionKind::Chorus),
+    ("B", SectionKind::Bridge),
+    ("T", SectionKind::Tag),
+    ("E", SectionKind::Outro),
+];
+
+/// The title of a section code with an optional number, e.g. `Verse 1` for `v1` with the
+/// OpenLyrics codes. Unknown codes are kept with spaces for underscores, e.g. `My Part`.
+pub(crate) fn section_title(name: &str, codes: &[(&str, SectionKind)]) -> String {
+    let code = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = &name[code.len()..];
+    match codes.iter().find(|(other, _)| *other == code) {
+        Some((_, kind)) if number.is_empty() => kind.format().into(),
+        Some((_, kind)) => format!("{} {}", kind.format(), number),
+        None => name.replace('_', " "),
+    }
+}
+
+/// The code of a section with its number, e.g. `v1`. Sections of other kinds are named by their
+/// title with underscores for spaces, since verse orders are separated by whitespace.
+pub(crate) fn section_code(section: &Section, codes: &[(&str, SectionKind)]) -> String {
+    match codes.iter().find(|(_, kind)| *kind == section.kind) {
+        Some((code, _)) => format!(
+            "{}{}",
+            code,
+            section
+                .number
+                .map(|number| number.to_string())
+                .unwrap_or_default()
+        ),
+        None => section
+            .title
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join("_"),
+    }
+}
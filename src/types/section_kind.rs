@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+/// Names of section kinds in several languages, compared in lowercase without punctuation.
+static NAMES: &[(&str, SectionKind)] = &[
+    ("intro", SectionKind::Intro),
+    ("introduction", SectionKind::Intro),
+    ("einleitung", SectionKind::Intro),
+    ("vorspiel", SectionKind::Intro),
+    ("verse", SectionKind::Verse),
+    ("v", SectionKind::Verse),
+    ("strophe", SectionKind::Verse),
+    ("vers", SectionKind::Verse),
+    ("couplet", SectionKind::Verse),
+    ("verso", SectionKind::Verse),
+    ("estrofa", SectionKind::Verse),
+    ("strofa", SectionKind::Verse),
+    ("prechorus", SectionKind::PreChorus),
+    ("pc", SectionKind::PreChorus),
+    ("prerefrain", SectionKind::PreChorus),
+    ("vorrefrain", SectionKind::PreChorus),
+    ("precoro", SectionKind::PreChorus),
+    ("chorus", SectionKind::Chorus),
+    ("c", SectionKind::Chorus),
+    ("refrain", SectionKind::Chorus),
+    ("kehrvers", SectionKind::Chorus),
+    ("estribillo", SectionKind::Chorus),
+    ("coro", SectionKind::Chorus),
+    ("ritornello", SectionKind::Chorus),
+    ("bridge", SectionKind::Bridge),
+    ("b", SectionKind::Bridge),
+    ("brücke", SectionKind::Bridge),
+    ("pont", SectionKind::Bridge),
+    ("puente", SectionKind::Bridge),
+    ("ponte", SectionKind::Bridge),
+    ("tag", SectionKind::Tag),
+    ("outro", SectionKind::Outro),
+    ("ending", SectionKind::Outro),
+    ("ende", SectionKind::Outro),
+    ("schluss", SectionKind::Outro),
+    ("coda", SectionKind::Outro),
+    ("final", SectionKind::Outro),
+    ("instrumental", SectionKind::Instrumental),
+    ("instr", SectionKind::Instrumental),
+    ("solo", SectionKind::Instrumental),
+    ("interlude", SectionKind::Interlude),
+    ("zwischenspiel", SectionKind::Interlude),
+    ("interludio", SectionKind::Interlude),
+    ("turnaround", SectionKind::Interlude),
+];
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum SectionKind {
+    Intro,
+    Verse,
+    PreChorus,
+    Chorus,
+    Bridge,
+    Tag,
+    Outro,
+    Instrumental,
+    Interlude,
+    /// Any section that is not recognized, only described by its title.
+    #[default]
+    Custom,
+}
+
+impl SectionKind {
+    /// Recognizes the kind and number of a section from its title, e.g. `Verse 1`, `Strophe 2`,
+    /// `Pre-Chorus` or `V1`.
+    pub fn parse(title: &str) -> (Self, Option<u8>) {
+        let title = title.trim().trim_end_matches(['.', ':', ')']);
+        let name = title.trim_end_matches(|c: char| c.is_ascii_digit());
+        let (name, number) = if name.len() < title.len() {
+            (name, title[name.len()..].parse::<u8>().ok())
+        } else {
+            let name = title.trim_start_matches(|c: char| c.is_ascii_digit());
+            (name, title[..title.len() - name.len()].parse::<u8>().ok())
+        };
+        let name = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let kind = NAMES
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map_or(SectionKind::Custom, |(_, kind)| *kind);
+        match kind {
+            SectionKind::Custom => (kind, None),
+            _ => (kind, number),
+        }
+    }
+
+    pub fn format(&self) -> &str {
+        match self {
+            SectionKind::Intro => "Intro",
+            SectionKind::Verse => "Verse",
+            SectionKind::PreChorus => "Pre-Chorus",
+            SectionKind::Chorus => "Chorus",
+            SectionKind::Bridge => "Bridge",
+            SectionKind::Tag => "Tag",
+            SectionKind::Outro => "Outro",
+            SectionKind::Instrumental => "Instrumental",
+            SectionKind::Interlude => "Interlude",
+            SectionKind::Custom => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn section_kind_parse() {
+        let inputs = [
+            "Verse 1",
+            "Chorus",
+            "Pre-Chorus",
+            "Strophe 2:",
+            "Refrain",
+            "1. Strophe",
+            "V3",
+            "Zwischenspiel",
+            "Brücke",
+            "Chords",
+        ];
+        let outputs = [
+            (SectionKind::Verse, Some(1)),
+            (SectionKind::Chorus, None),
+            (SectionKind::PreChorus, None),
+            (SectionKind::Verse, Some(2)),
+            (SectionKind::Chorus, None),
+            (SectionKind::Verse, Some(1)),
+            (SectionKind::Verse, Some(3)),
+            (SectionKind::Interlude, None),
+            (SectionKind::Bridge, None),
+            (SectionKind::Custom, None),
+        ];
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(&SectionKind::parse(input), output, "{}", input);
+        }
+    }
+}
@@ -1,28 +1,181 @@
 use serde::{Deserialize, Serialize};
 
-use super::{Section, SimpleChord};
+use crate::error::Error;
+
+use super::{
+    Arrangement, Chord, Fingering, Instrument, Key, KeyCandidate, Kind, Metadata, Section,
+    SimpleChord,
+};
 
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct Song {
     pub title: String,
-    pub key: Option<SimpleChord>,
+    pub key: Option<Key>,
     pub artist: Option<String>,
+    /// The language of the lyrics, e.g. `en`. Songs with translations list the language of each
+    /// index of `Part::languages` separated by commas, e.g. `en, de`, or an empty code for a
+    /// language without one.
     pub language: Option<String>,
+    pub capo: Option<u8>,
+    pub instrument: Option<Instrument>,
+    /// Custom fingerings for absolute chords, e.g. from ChordPro `{define}` directives.
+    #[serde(default)]
+    pub definitions: Vec<(Chord, Fingering)>,
+    #[serde(default)]
+    pub metadata: Metadata,
+    #[serde(default)]
+    pub arrangements: Vec<Arrangement>,
     pub sections: Vec<Section>,
 }
 
 impl Song {
-    pub fn transpose(&mut self, key: SimpleChord) -> &mut Self {
-        self.key = Some(key);
+    /// The language of each index of `Part::languages` as far as `language` lists them.
+    pub fn language_codes(&self) -> Vec<&str> {
+        self.language
+            .as_deref()
+            .map(|language| language.split(',').map(str::trim).collect())
+            .unwrap_or_default()
+    }
+
+    /// Changes the tonic of the key, the mode is kept.
+    pub fn transpose(&mut self, tonic: SimpleChord) -> &mut Self {
+        let mode = self.key.as_ref().map(Key::mode).unwrap_or_default();
+        self.key = Some(Key::new(tonic, mode));
         self
     }
 
+    /// The song with its sections in the performed order of the named arrangement (or the first
+    /// one if no name is given), songs without arrangements stay as they are.
+    pub fn expand(&self, name: Option<&str>) -> Result<Self, Error> {
+        let arrangement = match name {
+            Some(name) => Some(
+                self.arrangements
+                    .iter()
+                    .find(|arrangement| arrangement.name == name)
+                    .ok_or(Error::Other(format!("unknown arrangement, {}", name)))?,
+            ),
+            None => self.arrangements.first(),
+        };
+        let Some(arrangement) = arrangement else {
+            return Ok(self.clone());
+        };
+
+        let mut sections = Vec::default();
+        for step in &arrangement.steps {
+            let section = self
+                .sections
+                .iter()
+                .find(|section| section.title == step.section)
+                .ok_or(Error::Other(format!("unknown section, {}", step.section)))?;
+            sections.extend(std::iter::repeat_n(section.clone(), step.repeat as usize));
+        }
+        let mut result = self.clone();
+        result.sections = sections;
+        result.arrangements = Vec::default();
+        Ok(result)
+    }
+
+    /// All chords of the song in order, transposed to the key of the song.
+    pub fn chords(&self) -> Vec<Chord> {
+        let level = self.key.as_ref().map_or(0, |key| key.tonic().level());
+        self.sections
+            .iter()
+            .flat_map(|section| &section.lines)
+            .flat_map(|line| &line.parts)
+            .filter_map(|part| part.chord.clone())
+            .map(|chord| chord.transpose(level))
+            .collect()
+    }
+
+    /// Ranks key candidates by how well they fit the chords of the song.
+    pub fn estimate_keys(&self) -> Vec<KeyCandidate> {
+        KeyCandidate::estimate(&self.chords())
+    }
+
+    /// Sets the key to the best estimate if the song has none, this expects not yet normalized
+    /// chords.
+    pub fn detect_key(&mut self) -> &mut Self {
+        if self.key.is_none() {
+            self.key = self
+                .estimate_keys()
+                .into_iter()
+                .next()
+                .map(|candidate| candidate.key);
+        }
+        self
+    }
+
+    /// Fingerings for an absolute chord of the song on its instrument (guitar by default), custom
+    /// definitions come first.
+    pub fn fingerings(&self, chord: &Chord) -> Vec<Fingering> {
+        let mut result = self
+            .definitions
+            .iter()
+            .filter(|(other, _)| other == chord)
+            .map(|(_, fingering)| fingering.clone())
+            .collect::<Vec<Fingering>>();
+        let instrument = self.instrument.clone().unwrap_or_default();
+        for fingering in Fingering::generate(chord, &instrument) {
+            if !result.contains(&fingering) {
+                result.push(fingering);
+            }
+        }
+        result
+    }
+
+    /// The capo position that lets the most chords be played as open-position shapes in the
+    /// given key, lower positions win ties.
+    pub fn suggest_capo(&self, key: &Key) -> u8 {
+        let chords = self
+            .sections
+            .iter()
+            .flat_map(|section| &section.lines)
+            .flat_map(|line| &line.parts)
+            .filter_map(|part| part.chord.as_ref())
+            .collect::<Vec<&Chord>>();
+        (0..=MAX_CAPO)
+            .rev()
+            .max_by_key(|capo| {
+                let shape = key.shape(*capo).tonic().level();
+                chords
+                    .iter()
+                    .filter(|chord| {
+                        let root = chord.root().transpose(shape).level();
+                        OPEN_SHAPES.contains(&(root, chord.kind().clone()))
+                    })
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn normalize(&mut self) -> &mut Self {
         for section in &mut self.sections {
             if let Some(key) = &self.key {
-                section.normalize(&key);
+                section.normalize(key.tonic());
             }
         }
         self
     }
 }
+
+/// The highest capo position worth suggesting.
+const MAX_CAPO: u8 = 7;
+
+/// Roots and kinds of chords with common open-position guitar shapes.
+static OPEN_SHAPES: [(u8, Kind); 15] = [
+    (0, Kind::Major),
+    (3, Kind::Major),
+    (5, Kind::Major),
+    (7, Kind::Major),
+    (10, Kind::Major),
+    (0, Kind::Minor),
+    (5, Kind::Minor),
+    (7, Kind::Minor),
+    (0, Kind::Suspended2),
+    (5, Kind::Suspended2),
+    (0, Kind::Suspended4),
+    (5, Kind::Suspended4),
+    (7, Kind::Suspended4),
+    (0, Kind::Power),
+    (7, Kind::Power),
+];
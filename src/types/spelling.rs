@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Key, Mode, Note, SimpleChord};
+
+/// The policy used to choose between enharmonic spellings of a level.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum Spelling {
+    /// Spells the key as written and every level by its function in the key, which can result
+    /// in theoretical keys and double accidentals.
+    #[default]
+    Theoretical,
+    /// Prefers sharp keys and sharps for chromatic levels.
+    Sharps,
+    /// Prefers flat keys and flats for chromatic levels.
+    Flats,
+}
+
+impl Spelling {
+    /// The tonic of the key as spelled with this policy.
+    pub fn tonic(&self, key: &Key) -> Note {
+        if let (Spelling::Theoretical, Some(name)) = (self, key.name()) {
+            return name.clone();
+        }
+        let signature = key.signature().level();
+        let signature = match (self, signature) {
+            (Spelling::Sharps, 2) => Note::new(1, 0),
+            (Spelling::Sharps, 4) => Note::new(2, 1),
+            (Spelling::Sharps, 9) => Note::new(5, 1),
+            (Spelling::Flats, 2) => Note::new(2, -1),
+            (Spelling::Flats, 4) => Note::new(3, -1),
+            (Spelling::Flats, 9) => Note::new(6, -1),
+            (_, level) => CONVENTIONAL_SIGNATURES[level as usize].clone(),
+        };
+        let degree = Mode::Major
+            .scale()
+            .iter()
+            .position(|level| *level == key.mode().offset())
+            .unwrap_or_default();
+        Note::from_level(
+            signature.letter() + degree as u8,
+            signature.level() + key.mode().offset(),
+        )
+    }
+
+    /// Spells the normalized level in the key, scale tones get the letter of their scale degree.
+    pub fn spell(&self, level: &SimpleChord, key: &Key) -> Note {
+        let tonic = self.tonic(key);
+        let level = level.level();
+        let absolute = tonic.level() + level;
+        let scale = key.mode().scale();
+        let major = Mode::Major.scale();
+
+        if let Some(degree) = scale.iter().position(|other| *other == level) {
+            return Note::from_level(tonic.letter() + degree as u8, absolute);
+        }
+
+        if *self == Spelling::Theoretical {
+            let degree = major
+                .iter()
+                .position(|other| *other == level)
+                .or(if level == 6 { Some(3) } else { None })
+                .or_else(|| major.iter().position(|other| *other == level + 1))
+                .unwrap_or_default();
+            return Note::from_level(tonic.letter() + degree as u8, absolute);
+        }
+
+        let below = scale
+            .iter()
+            .rposition(|other| *other < level)
+            .unwrap_or_default();
+        let sharp = Note::from_level(tonic.letter() + below as u8, absolute);
+        let flat = Note::from_level(tonic.letter() + below as u8 + 1, absolute);
+        match (sharp.accidental().abs().cmp(&flat.accidental().abs()), self) {
+            (std::cmp::Ordering::Less, _) => sharp,
+            (std::cmp::Ordering::Greater, _) => flat,
+            (_, Spelling::Flats) => flat,
+            _ => sharp,
+        }
+    }
+}
+
+/// The major keys with the fewest accidentals per level of the tonic.
+static CONVENTIONAL_SIGNATURES: [Note; 12] = [
+    Note::new(0, 0),
+    Note::new(1, -1),
+    Note::new(1, 0),
+    Note::new(2, 0),
+    Note::new(3, -1),
+    Note::new(3, 0),
+    Note::new(4, -1),
+    Note::new(4, 0),
+    Note::new(5, 0),
+    Note::new(5, 1),
+    Note::new(6, 0),
+    Note::new(0, -1),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Chord;
+    use std::str::FromStr;
+
+    fn format(key: &str, chord: &str, spelling: Spelling) -> String {
+        let key = Key::try_from(key).unwrap();
+        Chord::from_str(chord)
+            .unwrap()
+            .normalize(key.tonic())
+            .format_spelled(&key, spelling)
+    }
+
+    #[test]
+    fn spell() {
+        let inputs = [
+            ("Gb", "B", Spelling::Theoretical),
+            ("C#", "Fm", Spelling::Theoretical),
+            ("Abm", "A", Spelling::Theoretical),
+            ("C", "Bb", Spelling::Theoretical),
+            ("C", "F#dim", Spelling::Theoretical),
+            ("Am", "G#dim", Spelling::Theoretical),
+            ("F#", "B", Spelling::Flats),
+            ("Gb", "Cb", Spelling::Sharps),
+            ("C", "C#", Spelling::Flats),
+            ("C", "Db", Spelling::Sharps),
+            ("Dm", "A#", Spelling::Sharps),
+        ];
+        let outputs = [
+            "Cb", "E#m", "Bbb", "Bb", "F#dim", "G#dim", "Cb", "B", "Db", "C#", "Bb",
+        ];
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(&format(input.0, input.1, input.2), output, "{:?}", input);
+        }
+
+        let key = Key::try_from("Gb").unwrap();
+        assert_eq!(key.format(), "Gb");
+        assert_eq!(key.format_spelled(Spelling::Sharps), "F#");
+        let key = Key::new(SimpleChord::new(6), Mode::Minor);
+        assert_eq!(key.format_spelled(Spelling::Sharps), "D#m");
+        assert_eq!(key.format_spelled(Spelling::Flats), "Ebm");
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Chord, Interval, Key, Note, Spelling};
+
+/// The lowest note of the left hand, C2.
+const LEFT_HAND_LOWEST: u8 = 36;
+
+/// The range of the right hand, E3 to C6.
+const RIGHT_HAND_RANGE: std::ops::RangeInclusive<u8> = 52..=84;
+
+/// Where the right hand starts without a previous voicing, middle C.
+const RIGHT_HAND_CENTER: u8 = 60;
+
+/// The most notes played by the right hand.
+const RIGHT_HAND_NOTES: usize = 4;
+
+/// A spelled note at a MIDI pitch.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct PianoNote {
+    pub note: Note,
+    pub pitch: u8,
+}
+
+impl PianoNote {
+    fn new(note: Note, pitch: u8) -> Self {
+        Self { note, pitch }
+    }
+
+    /// Writes the note with its octave, e.g. `C4` for middle C.
+    pub fn format(&self) -> String {
+        let natural = (Note::new(self.note.letter(), 0).level() + 9) % 12;
+        let octave = (self.pitch as i16 - natural as i16 - self.note.accidental() as i16) / 12 - 1;
+        format!("{}{}", self.note.format(), octave)
+    }
+}
+
+/// The notes of a chord on the piano split into left and right hand, from low to high.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct Voicing {
+    left: Vec<PianoNote>,
+    right: Vec<PianoNote>,
+}
+
+impl Voicing {
+    /// Voices the normalized chord in the key, the left hand plays the bass (and the fifth above
+    /// the root) and the right hand plays the remaining tones in the inversion closest to the
+    /// previous voicing.
+    pub fn new(chord: &Chord, key: &Key, previous: Option<&Voicing>) -> Self {
+        let tones = chord
+            .tones(key)
+            .into_iter()
+            .map(|tone| (tone.interval, tone.note))
+            .collect::<Vec<(Interval, Note)>>();
+        let bass = match chord.bass() {
+            Some(bass) => Spelling::default().spell(bass, key),
+            None => tones[0].1.clone(),
+        };
+
+        let bass_pitch = LEFT_HAND_LOWEST + pitch_class(&bass);
+        let mut left = vec![PianoNote::new(bass.clone(), bass_pitch)];
+        if let (None, Some((_, fifth))) = (
+            chord.bass(),
+            tones
+                .iter()
+                .find(|(interval, _)| *interval == Interval::new(5, 0)),
+        ) {
+            left.push(PianoNote::new(fifth.clone(), bass_pitch + 7));
+        }
+
+        let mut right = tones;
+        if right.len() > RIGHT_HAND_NOTES {
+            right.retain(|(interval, _)| *interval != Interval::new(5, 0));
+        }
+        if right.len() > RIGHT_HAND_NOTES {
+            right.retain(|(interval, _)| *interval != Interval::new(1, 0));
+        }
+        let mut right = right
+            .into_iter()
+            .map(|(_, note)| note)
+            .collect::<Vec<Note>>();
+        right.sort_by_key(pitch_class);
+
+        let right = candidates(&right)
+            .into_iter()
+            .filter(|candidate| candidate[0].pitch > left[left.len() - 1].pitch)
+            .min_by_key(|candidate| match previous {
+                Some(previous) => distance(candidate, &previous.right),
+                None => {
+                    (candidate[0].pitch as i16 - RIGHT_HAND_CENTER as i16).unsigned_abs() as u32
+                }
+            })
+            .unwrap_or_default();
+
+        Self { left, right }
+    }
+
+    pub fn left(&self) -> &[PianoNote] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[PianoNote] {
+        &self.right
+    }
+
+    /// Writes both hands, e.g. `C2 G2 | C4 E4 G4`.
+    pub fn format(&self) -> String {
+        let format = |notes: &[PianoNote]| {
+            notes
+                .iter()
+                .map(PianoNote::format)
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        format!("{} | {}", format(&self.left), format(&self.right))
+    }
+}
+
+/// The pitch class counted from C.
+fn pitch_class(note: &Note) -> u8 {
+    (note.level() + 9) % 12
+}
+
+/// Every inversion of the notes in close position at every octave within the right hand range.
+fn candidates(notes: &[Note]) -> Vec<Vec<PianoNote>> {
+    let mut result = Vec::default();
+    for inversion in 0..notes.len() {
+        for octave in 3..=6 {
+            let mut pitch = 12 * (octave + 1) + pitch_class(&notes[inversion]);
+            let mut candidate = Vec::with_capacity(notes.len());
+            for index in 0..notes.len() {
+                let note = &notes[(inversion + index) % notes.len()];
+                while pitch % 12 != pitch_class(note)
+                    || candidate
+                        .last()
+                        .is_some_and(|last: &PianoNote| last.pitch >= pitch)
+                {
+                    pitch += 1;
+                }
+                candidate.push(PianoNote::new(note.clone(), pitch));
+            }
+            if candidate
+                .iter()
+                .all(|note| RIGHT_HAND_RANGE.contains(&note.pitch))
+            {
+                result.push(candidate);
+            }
+        }
+    }
+    result
+}
+
+/// How far the hand moves, every note is matched with the closest note of the other voicing.
+fn distance(notes: &[PianoNote], others: &[PianoNote]) -> u32 {
+    let closest = |pitch: u8, others: &[PianoNote]| {
+        others
+            .iter()
+            .map(|other| (other.pitch as i16 - pitch as i16).unsigned_abs() as u32)
+            .min()
+            .unwrap_or_default()
+    };
+    notes
+        .iter()
+        .map(|note| closest(note.pitch, others))
+        .chain(others.iter().map(|other| closest(other.pitch, notes)))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn voice_leading() {
+        let key = Key::try_from("C").unwrap();
+        let mut previous: Option<Voicing> = None;
+        let mut outputs = Vec::default();
+        for chord in ["C", "F", "G/B", "Cmaj9"] {
+            let chord = Chord::from_str(chord).unwrap().normalize(key.tonic());
+            let voicing = Voicing::new(&chord, &key, previous.as_ref());
+            outputs.push(voicing.format());
+            previous = Some(voicing);
+        }
+        assert_eq!(
+            outputs,
+            [
+                "C2 G2 | C4 E4 G4",
+                "F2 C3 | C4 F4 A4",
+                "B2 | B3 D4 G4",
+                "C2 G2 | B3 C4 D4 E4"
+            ]
+        );
+
+        let key = Key::try_from("Gb").unwrap();
+        let chord = Chord::from_str("Cb").unwrap().normalize(key.tonic());
+        assert_eq!(
+            Voicing::new(&chord, &key, None).format(),
+            "Cb3 Gb3 | Cb4 Eb4 Gb4"
+        );
+    }
+}